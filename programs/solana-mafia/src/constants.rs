@@ -18,6 +18,22 @@ pub const MIN_DEPOSITS: [u64; 6] = [
     50_000_000_000, // 50 SOL - CharityFund
 ];
 
+/// Максимальные депозиты в lamports (10x базовой цены)
+pub const MAX_DEPOSITS: [u64; 6] = [
+    1_000_000_000,   // 1 SOL - TobaccoShop
+    5_000_000_000,   // 5 SOL - FuneralService
+    20_000_000_000,  // 20 SOL - CarWorkshop
+    50_000_000_000,  // 50 SOL - ItalianRestaurant
+    100_000_000_000, // 100 SOL - GentlemenClub
+    500_000_000_000, // 500 SOL - CharityFund
+];
+
+/// Кривая доходности: каждая следующая "базовая цена" сверх каталожной получает ставку на 10% ниже
+pub const YIELD_DECAY_PERCENT: u8 = 10;
+
+/// Кривая доходности: ставка на сверхдепозит не опускается ниже 25% от базовой
+pub const YIELD_FLOOR_PERCENT: u8 = 25;
+
 /// Дневные ставки в базисных пунктах (базовые доходности)
 pub const BUSINESS_RATES: [u16; 6] = [200, 180, 160, 140, 120, 100]; // 2.0%-1.0%, более дорогие = меньший %
 
@@ -25,14 +41,31 @@ pub const BUSINESS_RATES: [u16; 6] = [200, 180, 160, 140, 120, 100]; // 2.0%-1.0
 // UPGRADE SYSTEM - НОВАЯ МОДЕЛЬ
 // ============================================================================
 
-/// Максимальный уровень улучшения для каждого бизнеса
-pub const MAX_UPGRADE_LEVEL: u8 = 3;
+/// Жесткий предел уровней улучшения (размер массивов улучшений в GameConfig)
+pub const MAX_UPGRADE_LEVEL: u8 = 10;
 
-/// Множители стоимости улучшений (% от базовой стоимости бизнеса)
-pub const UPGRADE_COST_MULTIPLIERS: [u8; 3] = [20, 50, 100]; // 20%, 50%, 100%
+/// Количество уровней улучшения по умолчанию (GameConfig.max_upgrade_level)
+pub const DEFAULT_MAX_UPGRADE_LEVEL: u8 = 3;
+
+/// Множители стоимости улучшений (% от базовой стоимости бизнеса, 0 = уровень не используется)
+pub const UPGRADE_COST_MULTIPLIERS: [u16; MAX_UPGRADE_LEVEL as usize] = [20, 50, 100, 0, 0, 0, 0, 0, 0, 0]; // 20%, 50%, 100%
 
 /// Бонусы доходности за каждый уровень улучшения (в базисных пунктах)
-pub const UPGRADE_YIELD_BONUSES: [u16; 3] = [10, 25, 50]; // +0.1%, +0.25%, +0.5%
+pub const UPGRADE_YIELD_BONUSES: [u16; MAX_UPGRADE_LEVEL as usize] = [10, 25, 50, 0, 0, 0, 0, 0, 0, 0]; // +0.1%, +0.25%, +0.5%
+
+/// Что дает улучшение бизнесу
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum UpgradeModel {
+    Principal,       // Стоимость апгрейда добавляется к доходному депозиту
+    RateBonus,       // Ставка растет на upgrade_bonuses, доход только с базы
+    Both,            // И депозит, и ставка
+}
+
+/// Модель улучшений по типам бизнеса
+pub const UPGRADE_MODELS: [UpgradeModel; 6] = [UpgradeModel::Both; 6];
+
+/// Максимальная ставка бизнеса после всех улучшений (10% в день)
+pub const MAX_UPGRADED_RATE_BPS: u16 = 1_000;
 
 // ============================================================================
 // SLOT SYSTEM - НОВАЯ СИСТЕМА СЛОТОВ
@@ -77,8 +110,11 @@ pub const PREMIUM_SLOT_SELL_FEE_DISCOUNTS: [u8; 3] = [0, 50, 100]; // 0%, -50%,
 // SELL FEES - БЕЗ ИЗМЕНЕНИЙ
 // ============================================================================
 
+/// Количество записей в таблице комиссий продажи
+pub const EARLY_SELL_FEES_LEN: usize = 32;
+
 /// Early sell fees by days held (убывающая комиссия)
-pub const EARLY_SELL_FEES: [u8; 32] = [
+pub const EARLY_SELL_FEES: [u8; EARLY_SELL_FEES_LEN] = [
     25, 25, 25, 25, 25, 25, 25, // Days 0-6: 25%
     20, 20, 20, 20, 20, 20, 20, // Days 7-13: 20%
     15, 15, 15, 15, 15, 15, 15, // Days 14-20: 15%
@@ -96,6 +132,12 @@ pub const FINAL_SELL_FEE_PERCENT: u8 = 2;
 /// Earnings interval in seconds (24 hours)
 pub const EARNINGS_INTERVAL: i64 = 86_400;
 
+/// Сколько завершенных периодов копится без auto-claim (остальные сгорают)
+pub const DEFAULT_MAX_ACCRUAL_DAYS: u16 = 7;
+
+/// Максимальное значение max_accrual_days
+pub const MAX_ACCRUAL_DAYS_LIMIT: u16 = 365;
+
 /// Initial entry fee ($2 at $162/SOL = ~0.012 SOL) - Backend will control actual value
 pub const INITIAL_ENTRY_FEE: u64 = 12_345_679; // 0.012345679 SOL (~$2)
 
@@ -112,9 +154,28 @@ pub const TREASURY_FEE_PERCENT: u8 = 20; // 20%
 /// Claim earnings fee percentage (goes to team wallet)
 pub const CLAIM_EARNINGS_FEE_PERCENT: u8 = 2; // 2% от суммы claim
 
-/// Auto claim purchase cost (one-time payment for unlimited auto accumulation)
+/// Reinvest fee percentage (остается в treasury как резерв)
+pub const REINVEST_FEE_PERCENT: u8 = 1; // 1% от реинвестируемой суммы
+
+/// Max keeper tip for delegated claims (bps от чистой выплаты)
+pub const DEFAULT_MAX_KEEPER_TIP_BPS: u16 = 100; // 1%
+
+/// Auto claim subscription cost for 30 days
 pub const AUTO_CLAIM_COST: u64 = 50_000_000; // 0.05 SOL
 
+/// Количество тарифов подписки auto-claim
+pub const AUTO_CLAIM_TIERS: usize = 3;
+
+/// Длительность тарифов auto-claim (дни)
+pub const AUTO_CLAIM_TIER_DAYS: [u16; AUTO_CLAIM_TIERS] = [7, 30, 90];
+
+/// Стоимость тарифов auto-claim
+pub const AUTO_CLAIM_TIER_COSTS: [u64; AUTO_CLAIM_TIERS] = [
+    20_000_000,      // 0.02 SOL - 7 дней
+    AUTO_CLAIM_COST, // 0.05 SOL - 30 дней
+    120_000_000,     // 0.12 SOL - 90 дней
+];
+
 /// Upgrade costs (legacy - using multipliers now)
 pub const UPGRADE_COSTS: [u64; 3] = [
     100_000_000,   // 0.1 SOL for level 1
@@ -123,7 +184,7 @@ pub const UPGRADE_COSTS: [u64; 3] = [
 ];
 
 /// Upgrade bonuses (legacy - using UPGRADE_YIELD_BONUSES now)
-pub const UPGRADE_BONUSES: [u16; 3] = UPGRADE_YIELD_BONUSES;
+pub const UPGRADE_BONUSES: [u16; MAX_UPGRADE_LEVEL as usize] = UPGRADE_YIELD_BONUSES;
 
 
 // ============================================================================
@@ -134,11 +195,120 @@ pub const UPGRADE_BONUSES: [u16; 3] = UPGRADE_YIELD_BONUSES;
 pub const MAX_BUSINESSES_PER_PLAYER: u8 = 20;
 
 // ============================================================================
-// ADMIN CONFIGURATION - ДЛЯ МАКСИМАЛЬНОЙ ДЕЦЕНТРАЛИЗАЦИИ
+// ADMIN LIMITS - ЖЕСТКИЕ ГРАНИЦЫ ДЛЯ ИЗМЕНЕНИЯ GameConfig
 // ============================================================================
 
-/// 🔒 ЗАХАРДКОЖЕННЫЙ ADMIN PUBKEY - ЕДИНСТВЕННЫЙ КТО МОЖЕТ МЕНЯТЬ ENTRY FEE
-pub const HARDCODED_ADMIN_PUBKEY: Pubkey = anchor_lang::prelude::Pubkey::from_str_const("HLWTn3BYB3jvgquBG323XLyqzEj11H4N5m6EMpPGCCG6");
+/// Максимальная дневная ставка бизнеса (5% в день)
+pub const MAX_BUSINESS_RATE_BPS: u16 = 500;
+
+/// Минимально допустимый депозит бизнеса (0.01 SOL)
+pub const MIN_DEPOSIT_FLOOR: u64 = 10_000_000;
+
+/// Максимально допустимый минимальный депозит бизнеса (1000 SOL)
+pub const MAX_MIN_DEPOSIT: u64 = 1_000_000_000_000;
+
+/// Максимальный множитель стоимости улучшения (% от базовой стоимости)
+pub const MAX_UPGRADE_COST_MULTIPLIER: u16 = 1_000;
+
+/// Максимальный бонус доходности за уровень улучшения (basis points)
+pub const MAX_UPGRADE_BONUS_BPS: u16 = 200;
+
+/// Максимальная доля команды с депозитов
+pub const MAX_TREASURY_FEE_PERCENT: u8 = 50;
+
+/// Максимальная комиссия за claim
+pub const MAX_CLAIM_FEE_PERCENT: u8 = 10;
+
+/// Максимальная комиссия за реинвест
+pub const MAX_REINVEST_FEE_PERCENT: u8 = 10;
+
+/// Максимальный лимит tip keeper'а (bps)
+pub const MAX_KEEPER_TIP_BPS: u16 = 1_000;
+
+/// Максимальная комиссия ранней продажи
+pub const MAX_SELL_FEE_PERCENT: u8 = 50;
+
+/// Максимальная стоимость платного базового слота (% от цены бизнеса)
+pub const MAX_SLOT_UNLOCK_COST_PERCENT: u8 = 50;
+
+/// Максимальная стоимость премиум слота (50 SOL)
+pub const MAX_PREMIUM_SLOT_COST: u64 = 50_000_000_000;
+
+/// Максимальный бонус доходности премиум слота (basis points)
+pub const MAX_SLOT_YIELD_BONUS_BPS: u16 = 500;
+
+/// Максимальная стоимость auto claim (1 SOL)
+pub const MAX_AUTO_CLAIM_COST: u64 = 1_000_000_000;
+
+/// Максимальная длительность тарифа auto claim (дни)
+pub const MAX_AUTO_CLAIM_DAYS: u16 = 365;
+
+/// Задержка смены treasury wallet по умолчанию (48 часов)
+pub const DEFAULT_TREASURY_CHANGE_DELAY: i64 = 172_800;
+
+/// Минимальная задержка смены treasury wallet (24 часа)
+pub const MIN_TREASURY_CHANGE_DELAY: i64 = 86_400;
+
+/// Максимальная задержка смены treasury wallet (30 дней)
+pub const MAX_TREASURY_CHANGE_DELAY: i64 = 2_592_000;
+
+/// Максимальное количество получателей командной комиссии в FeeSplit
+pub const MAX_FEE_RECIPIENTS: usize = 5;
+
+/// Задержка изменений GameConfig по умолчанию (48 часов)
+pub const DEFAULT_CONFIG_TIMELOCK: i64 = 172_800;
+
+/// Минимальная задержка изменений GameConfig (24 часа)
+pub const MIN_CONFIG_TIMELOCK: i64 = 86_400;
+
+/// Максимальная задержка изменений GameConfig (30 дней)
+pub const MAX_CONFIG_TIMELOCK: i64 = 2_592_000;
+
+/// Максимальное количество разрешенных тиров депозита
+pub const MAX_DEPOSIT_TIERS: usize = 4;
+
+/// Разрешенные тиры депозита в строгом режиме (% от каталожной цены, 0 = не используется)
+pub const DEFAULT_DEPOSIT_TIERS: [u16; MAX_DEPOSIT_TIERS] = [100, 0, 0, 0]; // только каталожная цена
+
+/// Максимальный тир депозита и max_deposit (100x каталожной цены)
+pub const MAX_DEPOSIT_TIER_PERCENT: u16 = 10_000;
+
+/// Доля свободных резервов treasury, которую одна выплата может забрать в pro-rata режиме (10%)
+pub const DEFAULT_PRO_RATA_MAX_DRAW_BPS: u16 = 1_000;
+
+/// Максимальная глубина реферальной цепочки
+pub const MAX_REFERRAL_DEPTH: usize = 3;
+
+/// Реферальные бонусы по уровням (basis points от командной комиссии)
+pub const DEFAULT_REFERRAL_BONUS_BPS: [u16; MAX_REFERRAL_DEPTH] = [1_000, 500, 250]; // 10%, 5%, 2.5%
+
+/// Максимальная суммарная доля командной комиссии на реферальные бонусы (50%)
+pub const MAX_REFERRAL_TOTAL_BPS: u16 = 5_000;
+
+/// Максимальное количество участников AdminCouncil (битовая маска одобрений - u8)
+pub const MAX_COUNCIL_MEMBERS: usize = 7;
+
+// ============================================================================
+// EMERGENCY PAUSE - БИТЫ ОПЕРАЦИЙ
+// ============================================================================
+
+/// Создание бизнесов (create_business, create_business_with_level)
+pub const PAUSE_NEW_BUSINESSES: u8 = 0x01;
+
+/// Улучшения бизнесов
+pub const PAUSE_UPGRADES: u8 = 0x02;
+
+/// Claim earnings
+pub const PAUSE_CLAIMS: u8 = 0x04;
+
+/// Продажа бизнесов
+pub const PAUSE_SELLS: u8 = 0x08;
+
+/// Все операции
+pub const PAUSE_ALL: u8 = PAUSE_NEW_BUSINESSES | PAUSE_UPGRADES | PAUSE_CLAIMS | PAUSE_SELLS;
+
+/// Операции, разрешенные во время глобальной паузы по умолчанию (выход игроков)
+pub const DEFAULT_PAUSE_ALLOWLIST: u8 = PAUSE_SELLS;
 
 // ============================================================================
 // PDA SEEDS
@@ -148,3 +318,8 @@ pub const GAME_STATE_SEED: &[u8] = b"game_state";
 pub const GAME_CONFIG_SEED: &[u8] = b"game_config"; 
 pub const TREASURY_SEED: &[u8] = b"treasury";
 pub const PLAYER_SEED: &[u8] = b"player";
+pub const FEE_SPLIT_SEED: &[u8] = b"fee_split";
+pub const PENDING_CONFIG_SEED: &[u8] = b"pending_config";
+pub const ADMIN_COUNCIL_SEED: &[u8] = b"admin_council";
+pub const PROPOSAL_SEED: &[u8] = b"proposal";
+pub const CLAIM_DELEGATE_SEED: &[u8] = b"claim_delegate";
@@ -155,4 +155,156 @@ pub enum SolanaMafiaError {
 
     #[msg("Auto claim not purchased")]
     AutoClaimNotPurchased,
+
+    // ⚙️ CONFIG UPDATES
+    #[msg("Business rate out of allowed range")]
+    InvalidBusinessRate,
+
+    #[msg("Minimum deposit out of allowed range")]
+    InvalidMinDeposit,
+
+    #[msg("Upgrade schedule out of allowed range")]
+    InvalidUpgradeSchedule,
+
+    #[msg("Slot cost or bonus out of allowed range")]
+    InvalidSlotConfig,
+
+    #[msg("Auto claim tiers out of allowed range")]
+    InvalidAutoClaimCost,
+
+    #[msg("Registrations are closed")]
+    RegistrationsClosed,
+
+    // 👑 AUTHORITY
+    #[msg("Config is frozen - authority has been renounced")]
+    ConfigFrozen,
+
+    #[msg("No pending authority transfer")]
+    NoPendingAuthority,
+
+    #[msg("Signer is not the pending authority")]
+    UnauthorizedPendingAuthority,
+
+    // 🏦 TREASURY WALLET ROTATION
+    #[msg("Timelock delay out of allowed range")]
+    InvalidTimelockDelay,
+
+    #[msg("No pending treasury wallet change")]
+    NoPendingTreasuryChange,
+
+    #[msg("Treasury wallet change timelock has not expired yet")]
+    TreasuryChangeNotReady,
+
+    // 💸 FEE SPLIT
+    #[msg("Invalid fee split - shares must sum to 10000 bps with unique non-empty wallets")]
+    InvalidFeeSplit,
+
+    #[msg("Fee recipient accounts do not match fee split config")]
+    InvalidFeeRecipient,
+
+    // ⏳ CONFIG TIMELOCK
+    #[msg("Config change timelock has not expired yet")]
+    ConfigChangeNotReady,
+
+    // 🏛️ ADMIN COUNCIL
+    #[msg("Invalid council - unique non-empty members and 1 <= threshold <= members")]
+    InvalidCouncilConfig,
+
+    #[msg("Signer is not an admin council member")]
+    NotCouncilMember,
+
+    #[msg("Admin council is enabled - an approved proposal is required")]
+    CouncilApprovalRequired,
+
+    #[msg("Proposal does not have enough approvals")]
+    ProposalNotApproved,
+
+    #[msg("Proposal already approved by this member")]
+    ProposalAlreadyApproved,
+
+    #[msg("Proposal already executed")]
+    ProposalAlreadyExecuted,
+
+    #[msg("Proposal action does not match this instruction")]
+    ProposalActionMismatch,
+
+    #[msg("Proposal was created for a previous council composition")]
+    StaleProposal,
+
+    // 🏦 SOLVENCY
+    #[msg("Pro-rata draw must be between 1 and 10000 bps")]
+    InvalidProRataDraw,
+
+    #[msg("Player has no owed earnings")]
+    NoOwedEarnings,
+
+    #[msg("Player is not first in the debt queue")]
+    NotFirstInDebtQueue,
+
+    #[msg("Treasury has no free reserves to repay debt")]
+    TreasuryReservesEmpty,
+
+    // 🤝 REFERRALS
+    #[msg("Invalid referral config - depth or total bonus out of range")]
+    InvalidReferralConfig,
+
+    #[msg("No referral bonus to claim")]
+    NoReferralBonus,
+
+    // 🛡️ SLIPPAGE
+    #[msg("Total payment exceeds max_total_payment")]
+    MaxPaymentExceeded,
+
+    #[msg("Sell return is below min_return")]
+    MinReturnNotMet,
+
+    #[msg("Claim amount is below min_claim")]
+    MinClaimNotMet,
+
+    // 🏷️ STRICT PRICING
+    #[msg("Deposit amount does not match an allowed price tier")]
+    DepositNotAllowed,
+
+    #[msg("Invalid deposit tiers - must be increasing, start at >= 100% and stay within limit")]
+    InvalidDepositTiers,
+
+    // 📉 YIELD CURVE
+    #[msg("Deposit amount exceeds max deposit for this business type")]
+    DepositAboveMax,
+
+    #[msg("Invalid yield curve - max deposits must be within [min, 100x min], floor <= 100%")]
+    InvalidYieldCurve,
+
+    // ✂️ PARTIAL SELL
+    #[msg("Partial sell amount must be > 0 and leave at least the minimum deposit in base principal")]
+    PartialSellBelowMinimum,
+
+    // ⏱️ ACCRUAL
+    #[msg("Max accrual days out of allowed range")]
+    InvalidMaxAccrualDays,
+
+    // 🔁 AUTO CLAIM SUBSCRIPTION
+    #[msg("Invalid auto claim tier")]
+    InvalidAutoClaimTier,
+
+    // 🤖 CLAIM DELEGATES
+    #[msg("Keeper tip exceeds the allowed maximum")]
+    InvalidKeeperTip,
+
+    #[msg("Signer is not the keeper authorized by this claim delegate")]
+    UnauthorizedKeeper,
+
+    #[msg("Invalid claim delegate account")]
+    InvalidClaimDelegate,
+
+    #[msg("Invalid crank batch - expected (player, claim delegate, owner wallet) triples")]
+    InvalidCrankBatch,
+
+    // ⬇️ DOWNGRADE
+    #[msg("Only the most recent upgrade can be removed - upgrade again before the next downgrade")]
+    NoDowngradableUpgrade,
+
+    // 🔁 REINVEST
+    #[msg("Reinvested amount is below min_reinvested")]
+    MinReinvestNotMet,
 }
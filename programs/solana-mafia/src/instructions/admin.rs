@@ -27,6 +27,9 @@ pub fn initialize(ctx: Context<Initialize>, treasury_wallet: Pubkey) -> Result<(
 
     // Initialize Treasury PDA
     **treasury_pda = Treasury::new(ctx.bumps.treasury_pda);
+
+    // Initialize FeeSplit (пусто = все комиссии на treasury wallet)
+    **ctx.accounts.fee_split = FeeSplit::new(ctx.bumps.fee_split);
     
     msg!("🎮 Solana Mafia initialized!");
     msg!("Authority: {}", ctx.accounts.authority.key());
@@ -36,20 +39,527 @@ pub fn initialize(ctx: Context<Initialize>, treasury_wallet: Pubkey) -> Result<(
 }
 
 
-/// 🔒 Update entry fee (admin only) - ЗАХАРДКОЖЕННАЯ ПРОВЕРКА ДЛЯ ДЕЦЕНТРАЛИЗАЦИИ
-pub fn update_entry_fee(ctx: Context<crate::UpdateEntryFee>, new_fee_lamports: u64) -> Result<()> {
+/// 🔒 Проверка админа - сверяем с authority, сохраненным в GameConfig
+fn require_admin(game_config: &GameConfig, authority: &Pubkey) -> Result<()> {
+    game_config.require_authority(authority)
+}
+
+/// 🚨 Проверка emergency админа (тот же authority, отдельная ошибка)
+fn require_emergency_admin(game_config: &GameConfig, authority: &Pubkey) -> Result<()> {
+    if game_config.is_frozen() {
+        return Err(SolanaMafiaError::ConfigFrozen.into());
+    }
+    if *authority != game_config.authority {
+        return Err(SolanaMafiaError::UnauthorizedEmergencyAdmin.into());
+    }
+    Ok(())
+}
+
+/// 🏛️ Авторизация чувствительной операции: один authority, либо (если совет
+/// включен) участник совета с одобренным предложением на ровно эту операцию
+fn require_admin_action<'info>(
+    game_config: &GameConfig,
+    signer: &Pubkey,
+    admin_council: Option<&AdminCouncil>,
+    proposal: &mut Option<Box<Account<'info, Proposal>>>,
+    action: AdminAction,
+) -> Result<()> {
+    if !game_config.council_enabled {
+        return require_admin(game_config, signer);
+    }
+    if game_config.is_frozen() {
+        return Err(SolanaMafiaError::ConfigFrozen.into());
+    }
+
+    let (Some(admin_council), Some(proposal)) = (admin_council, proposal.as_mut()) else {
+        return Err(SolanaMafiaError::CouncilApprovalRequired.into());
+    };
+    admin_council.require_member(signer)?;
+
+    let clock = Clock::get()?;
+    proposal.consume(admin_council, &action, clock.unix_timestamp)?;
+
+    emit!(crate::ProposalExecuted {
+        id: proposal.id,
+        action,
+        executed_by: *signer,
+        executed_at: clock.unix_timestamp,
+    });
+    Ok(())
+}
+
+/// 📣 Эмит ConfigUpdated (значения в порядке аргументов инструкции)
+fn emit_config_updated(
+    authority: Pubkey,
+    parameter: ConfigParameter,
+    old_value: Vec<u64>,
+    new_value: Vec<u64>,
+) -> Result<()> {
+    emit!(crate::ConfigUpdated {
+        authority,
+        parameter,
+        old_value,
+        new_value,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+/// 🚪 Close new player registrations (admin only)
+///
+/// Мгновенно можно только закрыть регистрацию: это аварийный тормоз, который не
+/// трогает уже вложенные средства. Повторное открытие - только через
+/// timelock (ConfigChange::RegistrationsOpen).
+pub fn close_registrations(ctx: Context<crate::UpdateGameConfig>) -> Result<()> {
     let game_config = &mut ctx.accounts.game_config;
-    
-    // 🚨 ЗАХАРДКОЖЕННАЯ ПРОВЕРКА - ТОЛЬКО ОДИН ADMIN МОЖЕТ МЕНЯТЬ FEE!
-    if ctx.accounts.authority.key() != crate::constants::HARDCODED_ADMIN_PUBKEY {
-        return Err(SolanaMafiaError::UnauthorizedAdmin.into());
+    require_admin_action(
+        game_config,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.admin_council.as_deref(),
+        &mut ctx.accounts.proposal,
+        AdminAction::CloseRegistrations,
+    )?;
+
+    let was_open = game_config.registrations_open;
+    game_config.registrations_open = false;
+
+    emit_config_updated(
+        ctx.accounts.authority.key(),
+        ConfigParameter::RegistrationsOpen,
+        vec![was_open as u64],
+        vec![0],
+    )?;
+
+    msg!("🚪 Registrations closed");
+    Ok(())
+}
+
+/// 🚨 Set emergency pause state (admin only)
+pub fn set_pause(
+    ctx: Context<crate::SetPause>,
+    is_paused: bool,
+    pause_flags: u8,
+    pause_allowlist: u8,
+) -> Result<()> {
+    let game_state = &mut ctx.accounts.game_state;
+    if ctx.accounts.game_config.council_enabled {
+        require_admin_action(
+            &ctx.accounts.game_config,
+            &ctx.accounts.authority.key(),
+            ctx.accounts.admin_council.as_deref(),
+            &mut ctx.accounts.proposal,
+            AdminAction::SetPause { is_paused, pause_flags, pause_allowlist },
+        )?;
+    } else {
+        require_emergency_admin(&ctx.accounts.game_config, &ctx.accounts.authority.key())?;
     }
-    
-    // Update fee using the method
-    game_config.update_entry_fee(new_fee_lamports)?;
-    
-    msg!("💰 Entry fee updated to {} lamports ({:.4} SOL)", new_fee_lamports, new_fee_lamports as f64 / 1_000_000_000.0);
-    
+
+    game_state.set_pause(is_paused, pause_flags, pause_allowlist)?;
+
+    emit!(crate::PauseUpdated {
+        authority: ctx.accounts.authority.key(),
+        is_paused,
+        pause_flags,
+        pause_allowlist,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("🚨 Pause updated: paused={}, flags={:#04x}, allowlist={:#04x}", is_paused, pause_flags, pause_allowlist);
+    Ok(())
+}
+
+/// 👑 Propose new authority (step 1 of 2). Pubkey::default() cancels the pending transfer
+pub fn propose_authority(ctx: Context<crate::UpdateGameConfig>, new_authority: Pubkey) -> Result<()> {
+    let game_config = &mut ctx.accounts.game_config;
+    require_admin_action(
+        game_config,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.admin_council.as_deref(),
+        &mut ctx.accounts.proposal,
+        AdminAction::ProposeAuthority(new_authority),
+    )?;
+
+    game_config.pending_authority = new_authority;
+
+    emit!(crate::AuthorityProposed {
+        authority: game_config.authority,
+        pending_authority: new_authority,
+        proposed_at: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("👑 Authority transfer proposed: {} -> {}", game_config.authority, new_authority);
+    Ok(())
+}
+
+/// 👑 Accept authority (step 2 of 2) - signed by the proposed authority
+pub fn accept_authority(ctx: Context<crate::AcceptAuthority>) -> Result<()> {
+    let game_config = &mut ctx.accounts.game_config;
+    let game_state = &mut ctx.accounts.game_state;
+    let new_authority = ctx.accounts.new_authority.key();
+
+    if game_config.is_frozen() {
+        return Err(SolanaMafiaError::ConfigFrozen.into());
+    }
+    if game_config.pending_authority == Pubkey::default() {
+        return Err(SolanaMafiaError::NoPendingAuthority.into());
+    }
+    if game_config.pending_authority != new_authority {
+        return Err(SolanaMafiaError::UnauthorizedPendingAuthority.into());
+    }
+
+    let old_authority = game_config.authority;
+    game_config.authority = new_authority;
+    game_config.pending_authority = Pubkey::default();
+    game_state.authority = new_authority;
+
+    emit!(crate::AuthorityTransferred {
+        old_authority,
+        new_authority,
+        transferred_at: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("👑 Authority transferred: {} -> {}", old_authority, new_authority);
+    Ok(())
+}
+
+/// 🔥 Renounce authority - config is frozen permanently, no admin actions possible after this
+pub fn renounce_authority(ctx: Context<crate::RenounceAuthority>) -> Result<()> {
+    let game_config = &mut ctx.accounts.game_config;
+    let game_state = &mut ctx.accounts.game_state;
+    require_admin_action(
+        game_config,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.admin_council.as_deref(),
+        &mut ctx.accounts.proposal,
+        AdminAction::RenounceAuthority,
+    )?;
+
+    let old_authority = game_config.authority;
+    game_config.authority = Pubkey::default();
+    game_config.pending_authority = Pubkey::default();
+    game_state.authority = Pubkey::default();
+    if game_state.has_pending_treasury_change() {
+        game_state.cancel_treasury_wallet_change()?;
+    }
+
+    emit!(crate::AuthorityRenounced {
+        old_authority,
+        renounced_at: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("🔥 Authority renounced by {} - config frozen forever", old_authority);
     Ok(())
 }
 
+/// 🏦 Schedule new treasury wallet (admin only) - takes effect after treasury_change_delay
+pub fn schedule_treasury_wallet(ctx: Context<crate::UpdateTreasuryWallet>, new_wallet: Pubkey) -> Result<()> {
+    let game_state = &mut ctx.accounts.game_state;
+    require_admin_action(
+        &ctx.accounts.game_config,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.admin_council.as_deref(),
+        &mut ctx.accounts.proposal,
+        AdminAction::ScheduleTreasuryWallet(new_wallet),
+    )?;
+
+    let clock = Clock::get()?;
+    let available_at = game_state.schedule_treasury_wallet(new_wallet, clock.unix_timestamp)?;
+
+    emit!(crate::TreasuryWalletChangeScheduled {
+        current_wallet: game_state.treasury_wallet,
+        pending_wallet: new_wallet,
+        available_at,
+        scheduled_at: clock.unix_timestamp,
+    });
+
+    msg!("🏦 Treasury wallet change scheduled: {} -> {} (available at {})",
+         game_state.treasury_wallet, new_wallet, available_at);
+    Ok(())
+}
+
+/// 🏦 Cancel scheduled treasury wallet change (admin / council)
+pub fn cancel_treasury_wallet_change(ctx: Context<crate::UpdateTreasuryWallet>) -> Result<()> {
+    let game_state = &mut ctx.accounts.game_state;
+    require_admin_action(
+        &ctx.accounts.game_config,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.admin_council.as_deref(),
+        &mut ctx.accounts.proposal,
+        AdminAction::CancelTreasuryWalletChange(game_state.pending_treasury_wallet),
+    )?;
+
+    let cancelled_wallet = game_state.cancel_treasury_wallet_change()?;
+
+    emit!(crate::TreasuryWalletChangeCancelled {
+        current_wallet: game_state.treasury_wallet,
+        cancelled_wallet,
+        cancelled_at: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("🏦 Treasury wallet change to {} cancelled", cancelled_wallet);
+    Ok(())
+}
+
+/// 🏦 Apply scheduled treasury wallet change (permissionless once the timelock expired)
+pub fn apply_treasury_wallet_change(ctx: Context<crate::ApplyTreasuryWallet>) -> Result<()> {
+    let game_state = &mut ctx.accounts.game_state;
+    let clock = Clock::get()?;
+
+    let old_wallet = game_state.apply_treasury_wallet_change(clock.unix_timestamp)?;
+
+    emit!(crate::TreasuryWalletChangeApplied {
+        old_wallet,
+        new_wallet: game_state.treasury_wallet,
+        applied_at: clock.unix_timestamp,
+    });
+
+    msg!("🏦 Treasury wallet changed: {} -> {}", old_wallet, game_state.treasury_wallet);
+    Ok(())
+}
+
+/// ⏳ Update treasury wallet change delay (admin only)
+pub fn set_treasury_change_delay(ctx: Context<crate::UpdateTreasuryWallet>, delay: i64) -> Result<()> {
+    let game_state = &mut ctx.accounts.game_state;
+    require_admin_action(
+        &ctx.accounts.game_config,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.admin_council.as_deref(),
+        &mut ctx.accounts.proposal,
+        AdminAction::SetTreasuryChangeDelay(delay),
+    )?;
+
+    let old_delay = game_state.treasury_change_delay;
+    game_state.set_treasury_change_delay(delay)?;
+
+    emit_config_updated(
+        ctx.accounts.authority.key(),
+        ConfigParameter::TreasuryChangeDelay,
+        vec![old_delay as u64],
+        vec![delay as u64],
+    )?;
+
+    msg!("⏳ Treasury change delay updated to {} seconds", delay);
+    Ok(())
+}
+
+/// 💸 Set team fee recipients (admin only) - empty list sends all fees to treasury wallet
+pub fn set_fee_recipients(ctx: Context<crate::SetFeeRecipients>, recipients: Vec<FeeRecipient>) -> Result<()> {
+    let fee_split = &mut ctx.accounts.fee_split;
+    require_admin_action(
+        &ctx.accounts.game_config,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.admin_council.as_deref(),
+        &mut ctx.accounts.proposal,
+        AdminAction::SetFeeRecipients(recipients.clone()),
+    )?;
+
+    let old_recipients = fee_split.active_recipients().to_vec();
+    fee_split.set_recipients(&recipients)?;
+
+    emit!(crate::FeeRecipientsUpdated {
+        authority: ctx.accounts.authority.key(),
+        old_recipients,
+        new_recipients: recipients.clone(),
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("💸 Fee recipients updated: {} recipients", recipients.len());
+    Ok(())
+}
+
+/// ⏳ Queue a GameConfig change (admin only) - executable after config_timelock
+pub fn queue_config_change(ctx: Context<crate::QueueConfigChange>, change: ConfigChange) -> Result<()> {
+    let game_config = &mut ctx.accounts.game_config;
+    require_admin_action(
+        game_config,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.admin_council.as_deref(),
+        &mut ctx.accounts.proposal,
+        AdminAction::QueueConfigChange(change.clone()),
+    )?;
+
+    // Валидируем изменение заранее на копии конфига
+    let mut preview = (**game_config).clone();
+    preview.apply_change(&change)?;
+
+    let clock = Clock::get()?;
+    let id = game_config.next_config_change_id;
+    let executable_at = clock.unix_timestamp
+        .checked_add(game_config.config_timelock)
+        .ok_or(SolanaMafiaError::MathOverflow)?;
+
+    **ctx.accounts.pending_change = PendingConfigChange {
+        id,
+        change: change.clone(),
+        queued_by: ctx.accounts.authority.key(),
+        queued_at: clock.unix_timestamp,
+        executable_at,
+        bump: ctx.bumps.pending_change,
+    };
+    game_config.next_config_change_id = id
+        .checked_add(1)
+        .ok_or(SolanaMafiaError::MathOverflow)?;
+
+    emit!(crate::ConfigChangeQueued {
+        id,
+        change,
+        queued_by: ctx.accounts.authority.key(),
+        executable_at,
+        queued_at: clock.unix_timestamp,
+    });
+
+    msg!("⏳ Config change #{} queued, executable at {}", id, executable_at);
+    Ok(())
+}
+
+/// ⏳ Cancel a queued GameConfig change (admin only)
+pub fn cancel_config_change(ctx: Context<crate::CancelConfigChange>) -> Result<()> {
+    require_admin_action(
+        &ctx.accounts.game_config,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.admin_council.as_deref(),
+        &mut ctx.accounts.proposal,
+        AdminAction::CancelConfigChange(ctx.accounts.pending_change.id),
+    )?;
+
+    let pending_change = &ctx.accounts.pending_change;
+
+    emit!(crate::ConfigChangeCancelled {
+        id: pending_change.id,
+        change: pending_change.change.clone(),
+        cancelled_by: ctx.accounts.authority.key(),
+        cancelled_at: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("⏳ Config change #{} cancelled", pending_change.id);
+    Ok(())
+}
+
+/// ⏳ Execute a queued GameConfig change (permissionless once the timelock expired)
+pub fn execute_config_change(ctx: Context<crate::ExecuteConfigChange>) -> Result<()> {
+    let game_config = &mut ctx.accounts.game_config;
+    let pending_change = &ctx.accounts.pending_change;
+    let clock = Clock::get()?;
+
+    if game_config.is_frozen() {
+        return Err(SolanaMafiaError::ConfigFrozen.into());
+    }
+    if clock.unix_timestamp < pending_change.executable_at {
+        return Err(SolanaMafiaError::ConfigChangeNotReady.into());
+    }
+
+    let (parameter, old_value, new_value) = game_config.apply_change(&pending_change.change)?;
+
+    emit_config_updated(pending_change.queued_by, parameter, old_value, new_value)?;
+    emit!(crate::ConfigChangeExecuted {
+        id: pending_change.id,
+        change: pending_change.change.clone(),
+        executed_at: clock.unix_timestamp,
+    });
+
+    msg!("⏳ Config change #{} executed", pending_change.id);
+    Ok(())
+}
+
+/// 🏛️ Set admin council members and threshold. Empty members with threshold 0 disables the council
+pub fn set_admin_council(
+    ctx: Context<crate::SetAdminCouncil>,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    require_admin_action(
+        &ctx.accounts.game_config,
+        &ctx.accounts.authority.key(),
+        Some(&ctx.accounts.admin_council),
+        &mut ctx.accounts.proposal,
+        AdminAction::SetAdminCouncil { members: members.clone(), threshold },
+    )?;
+
+    let admin_council = &mut ctx.accounts.admin_council;
+    admin_council.set_members(&members, threshold)?;
+    admin_council.bump = ctx.bumps.admin_council;
+    ctx.accounts.game_config.council_enabled = !members.is_empty();
+
+    emit!(crate::AdminCouncilUpdated {
+        members: members.clone(),
+        threshold,
+        version: admin_council.version,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("🏛️ Admin council updated: {}-of-{}", threshold, members.len());
+    Ok(())
+}
+
+/// 🗳️ Create a proposal (council member only) - the proposer approves it automatically
+pub fn create_proposal(ctx: Context<crate::CreateProposal>, action: AdminAction) -> Result<()> {
+    let admin_council = &mut ctx.accounts.admin_council;
+    let proposer = ctx.accounts.proposer.key();
+    let member_index = admin_council.require_member(&proposer)?;
+    let clock = Clock::get()?;
+
+    let id = admin_council.next_proposal_id;
+    let proposal = &mut ctx.accounts.proposal;
+    ***proposal = Proposal {
+        id,
+        council_version: admin_council.version,
+        action: action.clone(),
+        proposer,
+        approvals: 0,
+        approval_count: 0,
+        created_at: clock.unix_timestamp,
+        executed: false,
+        executed_at: 0,
+        bump: ctx.bumps.proposal,
+    };
+    proposal.approve(admin_council, member_index)?;
+
+    admin_council.next_proposal_id = id
+        .checked_add(1)
+        .ok_or(SolanaMafiaError::MathOverflow)?;
+
+    emit!(crate::ProposalCreated {
+        id,
+        action,
+        proposer,
+        created_at: clock.unix_timestamp,
+    });
+
+    msg!("🗳️ Proposal #{} created by {}", id, proposer);
+    Ok(())
+}
+
+/// 🗳️ Approve a proposal (council member only)
+pub fn approve_proposal(ctx: Context<crate::ApproveProposal>) -> Result<()> {
+    let admin_council = &ctx.accounts.admin_council;
+    let proposal = &mut ctx.accounts.proposal;
+    let approver = ctx.accounts.approver.key();
+
+    let member_index = admin_council.require_member(&approver)?;
+    proposal.approve(admin_council, member_index)?;
+
+    emit!(crate::ProposalApproved {
+        id: proposal.id,
+        approver,
+        approval_count: proposal.approval_count,
+        threshold: admin_council.threshold,
+        approved_at: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("🗳️ Proposal #{} approved: {}/{}", proposal.id, proposal.approval_count, admin_council.threshold);
+    Ok(())
+}
+
+/// 🗳️ Close a proposal (proposer only) - cancels it if not executed and returns rent
+pub fn close_proposal(ctx: Context<crate::CloseProposal>) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+
+    emit!(crate::ProposalClosed {
+        id: proposal.id,
+        executed: proposal.executed,
+        closed_at: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("🗳️ Proposal #{} closed", proposal.id);
+    Ok(())
+}
@@ -4,39 +4,63 @@ use anchor_lang::system_program;
 use crate::state::*;
 use crate::constants::*;
 use crate::error::SolanaMafiaError;
+use crate::instructions::fees::{fee_recipient_accounts, transfer_team_fee};
+use crate::instructions::pricing::{
+    check_max_payment, check_min_amount, price_create_business, price_downgrade_business,
+    price_sell_business, price_upgrade_business, CreatePricing, UpgradePricing,
+};
+use crate::instructions::referral::{pay_referral_bonuses, validate_new_referrer};
+use crate::instructions::solvency::plan_treasury_payout;
 // Импорты контекстов убраны - используем прямо через lib.rs
 
 /// 🏪 Create business in specific slot (without NFT)
-pub fn create_business(
-    ctx: Context<crate::CreateBusinessInSlot>,
+pub fn create_business<'info>(
+    ctx: Context<'_, '_, '_, 'info, crate::CreateBusinessInSlot<'info>>,
     business_type: u8,
     deposit_amount: u64,
     slot_index: u8,
+    referrer: Option<Pubkey>,
+    max_total_payment: Option<u64>,
 ) -> Result<()> {
     let player = &mut ctx.accounts.player;
     let game_state = &mut ctx.accounts.game_state;
     let game_config = &ctx.accounts.game_config;
+    let fee_recipients = fee_recipient_accounts(&ctx.accounts.fee_split, ctx.remaining_accounts)?;
+    let referrer_accounts = &ctx.remaining_accounts[fee_recipients.len()..];
     let clock = Clock::get()?;
 
+    game_state.require_not_paused(PAUSE_NEW_BUSINESSES)?;
+    let mut entry_fee_paid = 0u64;
+
     // 🔧 FIX: Инициализировать слоты если игрок новый (created_at == 0)
     if player.created_at == 0 {
         msg!("🆕 Initializing new player slots...");
+        require!(game_config.registrations_open, SolanaMafiaError::RegistrationsClosed);
         
         // 🚨 ENTRY FEE: Взимаем entry fee как в create_player
         let current_total_players = game_state.total_players;
         let entry_fee = game_config.get_current_entry_fee(current_total_players);
-        
-        system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                system_program::Transfer {
-                    from: ctx.accounts.owner.to_account_info(),
-                    to: ctx.accounts.treasury_wallet.to_account_info(),
-                },
-            ),
+        entry_fee_paid = entry_fee;
+        let new_referrer = validate_new_referrer(&ctx.accounts.owner.key(), referrer, referrer_accounts)?;
+        let entry_team_fee = pay_referral_bonuses(
+            &ctx.accounts.system_program.to_account_info(),
+            &ctx.accounts.owner.to_account_info(),
+            ctx.accounts.owner.key(),
+            new_referrer,
+            referrer_accounts,
+            game_config,
             entry_fee,
         )?;
         
+        transfer_team_fee(
+            &ctx.accounts.system_program.to_account_info(),
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.treasury_wallet.to_account_info(),
+            &ctx.accounts.fee_split,
+            fee_recipients,
+            entry_team_fee,
+        )?;
+        
         // 🚨 ИСПРАВЛЕНО: Используем правильную инициализацию PlayerCompact::new()
         ***player = PlayerCompact::new(
             ctx.accounts.owner.key(),
@@ -46,6 +70,7 @@ pub fn create_business(
         
         // Устанавливаем has_paid_entry=true так как entry fee оплачен
         player.set_has_paid_entry(true);
+        player.referrer = new_referrer;
         
         // Обновляем game_state как в create_player
         game_state.add_player();
@@ -56,65 +81,38 @@ pub fn create_business(
         msg!("✅ New player initialized with 9 slots (3 free + 3 basic paid + 3 premium), entry fee: {} lamports", entry_fee);
     }
 
-    // Validate slot index
-    if slot_index >= MAX_REGULAR_SLOTS {
-        return Err(SolanaMafiaError::InvalidSlotIndex.into());
-    }
-
-    // Check if slot is available (все слоты разблокированы по умолчанию)
-    let slot = &player.business_slots[slot_index as usize];
-    if slot.business.is_some() {
-        return Err(SolanaMafiaError::SlotAlreadyOccupied.into());
-    }
-
-    // Validate business type
-    if (business_type as usize) >= BUSINESS_TYPES_COUNT {
-        return Err(SolanaMafiaError::InvalidBusinessType.into());
-    }
-
-    let business_enum = BusinessType::from_index(business_type).unwrap();
-    let min_deposit = business_enum.get_base_cost();
-    let daily_rate = business_enum.get_base_rate();
-
-    // Validate deposit amount
-    if deposit_amount < min_deposit {
-        return Err(SolanaMafiaError::InsufficientDeposit.into());
-    }
-
-    // 🏪 НОВОЕ: Проверяем стоимость слота заранее
-    let slot_cost = slot.get_slot_cost(deposit_amount);
-    let total_payment = deposit_amount
-        .checked_add(slot_cost)
-        .ok_or(SolanaMafiaError::MathOverflow)?;
+    // 🧮 Валидация и расчет цены (тот же код, что в quote_create_business)
+    let pricing = price_create_business(player, game_config, business_type, deposit_amount, slot_index, None)?;
+    check_max_payment(entry_fee_paid.saturating_add(pricing.total_payment), max_total_payment)?;
+    let slot_cost = pricing.slot_cost;
+    let team_fee = pricing.team_fee;
     
     if slot_cost > 0 {
         msg!("🏪 Slot cost required: {} lamports ({:.3} SOL)", slot_cost, slot_cost as f64 / 1_000_000_000.0);
     }
-
-    // Calculate fees: 20% to team wallet, 80% to treasury PDA (from business price only)
-    let team_fee = deposit_amount
-        .checked_mul(20) // 20% to team
-        .ok_or(SolanaMafiaError::MathOverflow)?
-        .checked_div(100)
-        .ok_or(SolanaMafiaError::MathOverflow)?;
-
-    let treasury_amount = deposit_amount
-        .checked_sub(team_fee)
-        .ok_or(SolanaMafiaError::InsufficientDeposit)?; // 80% to treasury PDA
+        
+    // 🤝 Реферальные бонусы из командной комиссии (не из стоимости слота)
+    let referral_team_fee = pay_referral_bonuses(
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner.key(),
+        player.referrer,
+        referrer_accounts,
+        game_config,
+        team_fee,
+    )?;
         
     // 🏪 Комиссия за слот идет полностью команде
-    let total_team_fee = team_fee.checked_add(slot_cost)
+    let total_team_fee = referral_team_fee.checked_add(slot_cost)
         .ok_or(SolanaMafiaError::MathOverflow)?;
 
     // Transfer team fee + slot cost to team wallet via CPI
-    system_program::transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.owner.to_account_info(),
-                to: ctx.accounts.treasury_wallet.to_account_info(),
-            },
-        ),
+    transfer_team_fee(
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.treasury_wallet.to_account_info(),
+        &ctx.accounts.fee_split,
+        fee_recipients,
         total_team_fee,
     )?;
 
@@ -127,20 +125,22 @@ pub fn create_business(
                 to: ctx.accounts.treasury_pda.to_account_info(),
             },
         ),
-        treasury_amount,
+        pricing.treasury_amount,
     )?;
 
     // 🔧 ИСПРАВЛЕНО: slot_cost - невозвратный донат, НЕ входит в стоимость бизнеса
     let business_value = deposit_amount; // Только депозит, БЕЗ slot_cost
     
     let business = Business::new(
-        business_enum,
+        pricing.business_type,
         business_value, // Только депозит - возвратная часть
+        pricing.daily_rate,
+        game_config.get_upgrade_model(pricing.business_type.to_index()),
         clock.unix_timestamp,
     );
 
     // 🏪 Используем новый метод для оплаты слота при необходимости
-    let actual_slot_cost = player.pay_slot_if_needed(slot_index as usize, deposit_amount)?;
+    let actual_slot_cost = player.pay_slot_if_needed(slot_index as usize, deposit_amount, game_config)?;
     
     // Place business in slot
     player.place_business_in_slot(slot_index as usize, business)?;
@@ -167,8 +167,8 @@ pub fn create_business(
         level: 0, // Базовая функция создает level 0
         base_cost: deposit_amount, // Только депозит (возвратная часть)
         slot_cost: actual_slot_cost,
-        total_paid: total_payment,
-        daily_rate,
+        total_paid: pricing.total_payment,
+        daily_rate: pricing.daily_rate,
         created_at: clock.unix_timestamp,
     });
 
@@ -176,51 +176,51 @@ pub fn create_business(
 }
 
 /// ⬆️ Upgrade business in slot (simplified without NFT)
-pub fn upgrade_business(
-    ctx: Context<crate::UpgradeBusinessInSlot>,
+pub fn upgrade_business<'info>(
+    ctx: Context<'_, '_, '_, 'info, crate::UpgradeBusinessInSlot<'info>>,
     slot_index: u8,
+    max_total_payment: Option<u64>,
 ) -> Result<()> {
     let player = &mut ctx.accounts.player;
-    let _game_state = &mut ctx.accounts.game_state;
-    let _game_config = &ctx.accounts.game_config;
+    let game_state = &mut ctx.accounts.game_state;
+    let game_config = &ctx.accounts.game_config;
+    let fee_recipients = fee_recipient_accounts(&ctx.accounts.fee_split, ctx.remaining_accounts)?;
     let clock = Clock::get()?;
 
-    // Validate slot
-    if slot_index >= MAX_REGULAR_SLOTS {
-        return Err(SolanaMafiaError::InvalidSlotIndex.into());
-    }
+    game_state.require_not_paused(PAUSE_UPGRADES)?;
 
-    let slot = &mut player.business_slots[slot_index as usize];
-    let current_business = slot.business.as_mut()
-        .ok_or(SolanaMafiaError::BusinessNotFound)?;
-
-    if !current_business.is_active {
-        return Err(SolanaMafiaError::BusinessNotActive.into());
-    }
+    // 🧮 Валидация и расчет цены (тот же код, что в quote_upgrade_business)
+    let UpgradePricing { next_level, upgrade_cost, team_fee, treasury_amount, .. } =
+        price_upgrade_business(player, game_config, slot_index)?;
+    check_max_payment(upgrade_cost, max_total_payment)?;
 
-    // Check if can upgrade
-    let next_level = current_business.upgrade_level + 1;
-    if next_level >= MAX_UPGRADE_LEVEL {
-        return Err(SolanaMafiaError::MaxLevelReached.into());
-    }
+    transfer_team_fee(
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.player_owner.to_account_info(),
+        &ctx.accounts.treasury_wallet.to_account_info(),
+        &ctx.accounts.fee_split,
+        fee_recipients,
+        team_fee,
+    )?;
 
-    // Calculate upgrade cost
-    let upgrade_cost = current_business.get_upgrade_cost(next_level)?;
-    
-    // Transfer upgrade cost to treasury via CPI
     system_program::transfer(
         CpiContext::new(
             ctx.accounts.system_program.to_account_info(),
             system_program::Transfer {
                 from: ctx.accounts.player_owner.to_account_info(),
-                to: ctx.accounts.treasury_wallet.to_account_info(),
+                to: ctx.accounts.treasury_pda.to_account_info(),
             },
         ),
-        upgrade_cost,
+        treasury_amount,
     )?;
 
+    // 🏦 Накопленное по старой ставке фиксируем до апгрейда
+    player.bank_slot_earnings(slot_index as usize, clock.unix_timestamp, game_config)?;
+
     // Upgrade business
-    current_business.upgrade_to_level(next_level, upgrade_cost)?;
+    let current_business = player.business_slots[slot_index as usize].business.as_mut()
+        .ok_or(SolanaMafiaError::BusinessNotFound)?;
+    current_business.upgrade_to_level(next_level, upgrade_cost, treasury_amount, game_config)?;
     let new_daily_rate = current_business.daily_rate;
 
     // 🚨 ИСПРАВЛЕНО: Используем u64 напрямую без конвертации
@@ -233,6 +233,9 @@ pub fn upgrade_business(
         .checked_add(upgrade_cost)
         .ok_or(SolanaMafiaError::MathOverflow)?;
 
+    game_state.add_investment(upgrade_cost);
+    game_state.add_treasury_collection(team_fee);
+
     emit!(crate::BusinessUpgradedInSlot {
         player: ctx.accounts.player_owner.key(),
         slot_index,
@@ -250,52 +253,33 @@ pub fn upgrade_business(
 pub fn sell_business(
     ctx: Context<crate::SellBusinessFromSlot>,
     slot_index: u8,
+    min_return: Option<u64>,
 ) -> Result<()> {
     let player = &mut ctx.accounts.player;
     let game_state = &mut ctx.accounts.game_state;
+    let game_config = &ctx.accounts.game_config;
     let clock = Clock::get()?;
 
-    // Validate slot
-    if slot_index >= MAX_REGULAR_SLOTS {
-        return Err(SolanaMafiaError::InvalidSlotIndex.into());
-    }
+    game_state.require_not_paused(PAUSE_SELLS)?;
 
-    let slot = &mut player.business_slots[slot_index as usize];
-    let business = slot.business.take()
-        .ok_or(SolanaMafiaError::BusinessNotFound)?;
+    // 🧮 Валидация и расчет возврата (тот же код, что в quote_sell_business)
+    let pricing = price_sell_business(player, game_config, slot_index, None, clock.unix_timestamp)?;
 
     // 🔧 CRITICAL FIX: Reset slot occupied flag after removing business
+    let slot = &mut player.business_slots[slot_index as usize];
+    slot.business = None;
     slot.set_has_business(false);
 
-    if !business.is_active {
-        return Err(SolanaMafiaError::BusinessNotActive.into());
-    }
-
-    // Calculate how long business was held
-    let days_held = (clock.unix_timestamp - business.created_at) / 86400;
-    let days_held_capped = std::cmp::min(days_held as usize, EARLY_SELL_FEES.len() - 1);
-
-    // Calculate sell fee with slot discount
-    let base_fee_percent = if days_held_capped < EARLY_SELL_FEES.len() {
-        EARLY_SELL_FEES[days_held_capped]
-    } else {
-        FINAL_SELL_FEE_PERCENT
-    };
-
-    let slot_discount = slot.get_sell_fee_discount();
-    let final_fee_percent = base_fee_percent.saturating_sub(slot_discount);
-
-    // Calculate return amount
-    let total_invested = business.get_total_investment_for_refund();
-    let sell_fee = total_invested
-        .checked_mul(final_fee_percent as u64)
-        .ok_or(SolanaMafiaError::MathOverflow)?
-        .checked_div(100)
-        .ok_or(SolanaMafiaError::MathOverflow)?;
-
-    let return_amount = total_invested
-        .checked_sub(sell_fee)
-        .ok_or(SolanaMafiaError::MathOverflow)?;
+    // 🏦 Не опускаем treasury ниже rent-exempt минимума, остаток - в долг игроку
+    let return_amount = plan_treasury_payout(
+        game_state,
+        game_config,
+        player,
+        &ctx.accounts.treasury_pda.to_account_info(),
+        pricing.refund_amount,
+        0,
+    )?;
+    check_min_amount(return_amount, min_return, SolanaMafiaError::MinReturnNotMet)?;
 
     // Return funds to player from treasury PDA using manual lamports manipulation
     // (System Program can't transfer from accounts with data, so we do it manually)
@@ -304,7 +288,7 @@ pub fn sell_business(
 
     // 🔧 КРИТИЧЕСКОЕ ИСПРАВЛЕНИЕ: Уменьшаем total_invested при продаже
     player.total_invested = player.total_invested
-        .checked_sub(total_invested)
+        .checked_sub(pricing.total_invested)
         .ok_or(SolanaMafiaError::MathOverflow)?;
 
     // Update statistics
@@ -313,12 +297,72 @@ pub fn sell_business(
     emit!(crate::BusinessSoldFromSlot {
         player: ctx.accounts.player_owner.key(),
         slot_index,
-        business_type: business.business_type.to_index() as u8,
-        total_invested,
-        days_held: days_held as u64,
-        base_fee_percent,
-        slot_discount,
-        final_fee_percent,
+        business_type: pricing.business_type,
+        total_invested: pricing.total_invested,
+        days_held: pricing.days_held,
+        base_fee_percent: pricing.base_fee_percent,
+        slot_discount: pricing.slot_discount,
+        final_fee_percent: pricing.final_fee_percent,
+        return_amount,
+        sold_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// ✂️ Sell part of a business principal, keeping the slot and the holding-time fee tier
+pub fn sell_business_partial(
+    ctx: Context<crate::SellBusinessFromSlot>,
+    slot_index: u8,
+    amount: u64,
+    min_return: Option<u64>,
+) -> Result<()> {
+    let player = &mut ctx.accounts.player;
+    let game_state = &mut ctx.accounts.game_state;
+    let game_config = &ctx.accounts.game_config;
+    let clock = Clock::get()?;
+
+    game_state.require_not_paused(PAUSE_SELLS)?;
+
+    // 🧮 Тот же расчет комиссии, что и в sell_business (от created_at)
+    let pricing = price_sell_business(player, game_config, slot_index, Some(amount), clock.unix_timestamp)?;
+
+    // 🏦 Накопленное на старый депозит фиксируем до вывода
+    player.bank_slot_earnings(slot_index as usize, clock.unix_timestamp, game_config)?;
+
+    let business = player.business_slots[slot_index as usize].business.as_mut()
+        .ok_or(SolanaMafiaError::BusinessNotFound)?;
+    business.withdraw_principal(amount)?;
+
+    // 🏦 Не опускаем treasury ниже rent-exempt минимума, остаток - в долг игроку
+    let return_amount = plan_treasury_payout(
+        game_state,
+        game_config,
+        player,
+        &ctx.accounts.treasury_pda.to_account_info(),
+        pricing.refund_amount,
+        0,
+    )?;
+    check_min_amount(return_amount, min_return, SolanaMafiaError::MinReturnNotMet)?;
+
+    **ctx.accounts.treasury_pda.to_account_info().try_borrow_mut_lamports()? -= return_amount;
+    **ctx.accounts.player_owner.to_account_info().try_borrow_mut_lamports()? += return_amount;
+
+    player.total_invested = player.total_invested
+        .checked_sub(amount)
+        .ok_or(SolanaMafiaError::MathOverflow)?;
+
+    game_state.add_withdrawal(return_amount);
+
+    emit!(crate::BusinessPartiallySold {
+        player: ctx.accounts.player_owner.key(),
+        slot_index,
+        business_type: pricing.business_type,
+        amount,
+        remaining_invested: pricing.remaining_invested,
+        days_held: pricing.days_held,
+        final_fee_percent: pricing.final_fee_percent,
+        slot_discount: pricing.slot_discount,
         return_amount,
         sold_at: clock.unix_timestamp,
     });
@@ -326,43 +370,115 @@ pub fn sell_business(
     Ok(())
 }
 
+/// ⬇️ Remove the top upgrade level, refund its cost minus the early-sell fee
+pub fn downgrade_business(
+    ctx: Context<crate::DowngradeBusinessInSlot>,
+    slot_index: u8,
+    min_return: Option<u64>,
+) -> Result<()> {
+    let player = &mut ctx.accounts.player;
+    let game_state = &mut ctx.accounts.game_state;
+    let game_config = &ctx.accounts.game_config;
+    let clock = Clock::get()?;
+
+    game_state.require_not_paused(PAUSE_SELLS)?;
+
+    let pricing = price_downgrade_business(player, game_config, slot_index, clock.unix_timestamp)?;
+
+    // 🏦 Накопленное по старой ставке фиксируем до даунгрейда
+    player.bank_slot_earnings(slot_index as usize, clock.unix_timestamp, game_config)?;
+
+    let business = player.business_slots[slot_index as usize].business.as_mut()
+        .ok_or(SolanaMafiaError::BusinessNotFound)?;
+    business.remove_top_upgrade()?;
+    let new_daily_rate = business.daily_rate;
+
+    // 🏦 Не опускаем treasury ниже rent-exempt минимума, остаток - в долг игроку
+    let return_amount = plan_treasury_payout(
+        game_state,
+        game_config,
+        player,
+        &ctx.accounts.treasury_pda.to_account_info(),
+        pricing.refund_amount,
+        0,
+    )?;
+    check_min_amount(return_amount, min_return, SolanaMafiaError::MinReturnNotMet)?;
+
+    **ctx.accounts.treasury_pda.to_account_info().try_borrow_mut_lamports()? -= return_amount;
+    **ctx.accounts.player_owner.to_account_info().try_borrow_mut_lamports()? += return_amount;
+
+    player.total_invested = player.total_invested
+        .checked_sub(pricing.upgrade_cost)
+        .ok_or(SolanaMafiaError::MathOverflow)?;
+    player.total_upgrade_spent = player.total_upgrade_spent.saturating_sub(pricing.upgrade_cost);
+
+    game_state.add_withdrawal(return_amount);
+
+    emit!(crate::BusinessDowngraded {
+        player: ctx.accounts.player_owner.key(),
+        slot_index,
+        old_level: pricing.old_level,
+        new_level: pricing.old_level - 1,
+        upgrade_cost: pricing.upgrade_cost,
+        final_fee_percent: pricing.final_fee_percent,
+        slot_discount: pricing.slot_discount,
+        return_amount,
+        new_daily_rate,
+        downgraded_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
 /// 🆕 Create business with target level (immediate upgrades)
-pub fn create_business_with_level(
-    ctx: Context<crate::CreateBusinessInSlot>,
+pub fn create_business_with_level<'info>(
+    ctx: Context<'_, '_, '_, 'info, crate::CreateBusinessInSlot<'info>>,
     business_type: u8,
     deposit_amount: u64,
     slot_index: u8,
     target_level: u8,
+    referrer: Option<Pubkey>,
+    max_total_payment: Option<u64>,
 ) -> Result<()> {
     let player = &mut ctx.accounts.player;
     let game_state = &mut ctx.accounts.game_state;
     let game_config = &ctx.accounts.game_config;
+    let fee_recipients = fee_recipient_accounts(&ctx.accounts.fee_split, ctx.remaining_accounts)?;
+    let referrer_accounts = &ctx.remaining_accounts[fee_recipients.len()..];
     let clock = Clock::get()?;
 
-    // Validate target level
-    if target_level > MAX_UPGRADE_LEVEL {
-        return Err(SolanaMafiaError::MaxLevelReached.into());
-    }
+    game_state.require_not_paused(PAUSE_NEW_BUSINESSES)?;
+    let mut entry_fee_paid = 0u64;
 
     // 🔧 FIX: Инициализировать слоты если игрок новый (код такой же как в create_business)
     if player.created_at == 0 {
         msg!("🆕 Initializing new player slots...");
+        require!(game_config.registrations_open, SolanaMafiaError::RegistrationsClosed);
         
         // 🚨 ENTRY FEE: Взимаем entry fee
         let current_total_players = game_state.total_players;
         let entry_fee = game_config.get_current_entry_fee(current_total_players);
-        
-        system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                system_program::Transfer {
-                    from: ctx.accounts.owner.to_account_info(),
-                    to: ctx.accounts.treasury_wallet.to_account_info(),
-                },
-            ),
+        entry_fee_paid = entry_fee;
+        let new_referrer = validate_new_referrer(&ctx.accounts.owner.key(), referrer, referrer_accounts)?;
+        let entry_team_fee = pay_referral_bonuses(
+            &ctx.accounts.system_program.to_account_info(),
+            &ctx.accounts.owner.to_account_info(),
+            ctx.accounts.owner.key(),
+            new_referrer,
+            referrer_accounts,
+            game_config,
             entry_fee,
         )?;
         
+        transfer_team_fee(
+            &ctx.accounts.system_program.to_account_info(),
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.treasury_wallet.to_account_info(),
+            &ctx.accounts.fee_split,
+            fee_recipients,
+            entry_team_fee,
+        )?;
+        
         // 🚨 ИСПРАВЛЕНО: Используем правильную инициализацию PlayerCompact::new()
         ***player = PlayerCompact::new(
             ctx.accounts.owner.key(),
@@ -372,6 +488,7 @@ pub fn create_business_with_level(
         
         // Устанавливаем has_paid_entry=true так как entry fee оплачен
         player.set_has_paid_entry(true);
+        player.referrer = new_referrer;
         
         game_state.add_player();
         game_state.total_treasury_collected = game_state.total_treasury_collected
@@ -381,77 +498,31 @@ pub fn create_business_with_level(
         msg!("✅ New player initialized with 9 slots, entry fee: {} lamports", entry_fee);
     }
 
-    // Validate slot and business type (код как в create_business)
-    if slot_index >= MAX_REGULAR_SLOTS {
-        return Err(SolanaMafiaError::InvalidSlotIndex.into());
-    }
-
-    let slot = &player.business_slots[slot_index as usize];
-    if slot.business.is_some() {
-        return Err(SolanaMafiaError::SlotAlreadyOccupied.into());
-    }
-
-    if (business_type as usize) >= BUSINESS_TYPES_COUNT {
-        return Err(SolanaMafiaError::InvalidBusinessType.into());
-    }
-
-    let business_enum = BusinessType::from_index(business_type).unwrap();
-    let base_cost = business_enum.get_base_cost();
-    
-    // 🆕 Рассчитываем стоимость апгрейдов
-    let mut upgrade_costs = [0u64; 3];
-    for level in 1..=target_level {
-        let multiplier = UPGRADE_COST_MULTIPLIERS[(level - 1) as usize];
-        upgrade_costs[(level - 1) as usize] = base_cost
-            .checked_mul(multiplier as u64)
-            .and_then(|x| x.checked_div(100))
-            .ok_or(SolanaMafiaError::MathOverflow)?;
-    }
-
-    // 🔧 ИСПРАВЛЕНИЕ: deposit_amount уже включает полную стоимость (base + upgrades)
-    // Проверяем минимум базовой стоимости
-    if deposit_amount < base_cost {
-        return Err(SolanaMafiaError::InsufficientDeposit.into());
-    }
-    
-    // Валидация что deposit_amount соответствует ожидаемой цене
-    let total_upgrade_cost: u64 = upgrade_costs.iter().take(target_level as usize).sum();
-    let expected_total_cost = base_cost
-        .checked_add(total_upgrade_cost)
-        .ok_or(SolanaMafiaError::MathOverflow)?;
-    
-    // Логируем для отладки
-    msg!("💰 Price validation: deposit={}, base={}, upgrades={}, expected={}", 
-         deposit_amount, base_cost, total_upgrade_cost, expected_total_cost);
-
-    // 🏪 Проверяем стоимость слота
-    let slot_cost = slot.get_slot_cost(deposit_amount);
-    // 🔧 ИСПРАВЛЕНО: slot_cost - невозвратный донат, НЕ входит в стоимость бизнеса
-    let _business_value = deposit_amount; // Только депозит, БЕЗ slot_cost
-
-    // Распределение платежей
-    let team_fee = deposit_amount
-        .checked_mul(20)
-        .ok_or(SolanaMafiaError::MathOverflow)?
-        .checked_div(100)
-        .ok_or(SolanaMafiaError::MathOverflow)?;
-
-    let treasury_amount = deposit_amount
-        .checked_sub(team_fee)
-        .ok_or(SolanaMafiaError::InsufficientDeposit)?;
+    // 🧮 Валидация и расчет цены (тот же код, что в quote_create_business)
+    // deposit_amount должен ровно равняться base + upgrades (иначе UpgradeCostMismatch)
+    let pricing = price_create_business(player, game_config, business_type, deposit_amount, slot_index, Some(target_level))?;
+    check_max_payment(entry_fee_paid.saturating_add(pricing.total_payment), max_total_payment)?;
+    let CreatePricing { business_type: business_enum, base_cost, daily_rate, upgrade_costs, slot_cost, team_fee, treasury_amount, .. } = pricing;
+    let referral_team_fee = pay_referral_bonuses(
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner.key(),
+        player.referrer,
+        referrer_accounts,
+        game_config,
+        team_fee,
+    )?;
         
-    let total_team_fee = team_fee.checked_add(slot_cost)
+    let total_team_fee = referral_team_fee.checked_add(slot_cost)
         .ok_or(SolanaMafiaError::MathOverflow)?;
 
     // Переводы
-    system_program::transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.owner.to_account_info(),
-                to: ctx.accounts.treasury_wallet.to_account_info(),
-            },
-        ),
+    transfer_team_fee(
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.treasury_wallet.to_account_info(),
+        &ctx.accounts.fee_split,
+        fee_recipients,
         total_team_fee,
     )?;
 
@@ -471,16 +542,19 @@ pub fn create_business_with_level(
         Business::create_upgraded(
             business_enum,
             base_cost,
+            daily_rate,
             target_level,
             upgrade_costs,
+            game_config,
             clock.unix_timestamp,
         )?
     } else {
-        Business::new(business_enum, base_cost, clock.unix_timestamp)
+        let upgrade_model = game_config.get_upgrade_model(business_enum.to_index());
+        Business::new(business_enum, base_cost, daily_rate, upgrade_model, clock.unix_timestamp)
     };
 
     // Оплатить слот и поместить бизнес
-    let actual_slot_cost = player.pay_slot_if_needed(slot_index as usize, deposit_amount)?;
+    let actual_slot_cost = player.pay_slot_if_needed(slot_index as usize, deposit_amount, game_config)?;
     player.place_business_in_slot(slot_index as usize, business)?;
 
     // 🔧 ИСПРАВЛЕНО: Учитываем только депозит (slot_cost = невозвратный донат)
@@ -0,0 +1,204 @@
+// 🤖 Делегированные claim'ы: игрок разрешает keeper'у клеймить за него (ClaimDelegate).
+// Earnings всегда уходят на кошелек игрока, keeper получает tip (bps, лимит в GameConfig).
+// crank_claim_batch: remaining accounts = [получатели FeeSplit..., (player, claim_delegate, owner wallet)...]
+
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::*;
+use crate::error::SolanaMafiaError;
+use crate::instructions::earnings::{pay_out_earnings, PayoutAccounts};
+use crate::instructions::fees::fee_recipient_accounts;
+
+/// 🤝 Назначить (или заменить) keeper'а для claim'ов игрока
+pub fn set_claim_delegate(
+    ctx: Context<crate::SetClaimDelegate>,
+    keeper: Pubkey,
+    tip_bps: u16,
+) -> Result<()> {
+    require!(
+        tip_bps <= ctx.accounts.game_config.max_keeper_tip_bps,
+        SolanaMafiaError::InvalidKeeperTip
+    );
+    let clock = Clock::get()?;
+    let player = ctx.accounts.player_owner.key();
+
+    let delegate = &mut ctx.accounts.claim_delegate;
+    delegate.player = player;
+    delegate.keeper = keeper;
+    delegate.tip_bps = tip_bps;
+    delegate.created_at = clock.unix_timestamp;
+    delegate.bump = ctx.bumps.claim_delegate;
+
+    emit!(crate::ClaimDelegateSet {
+        player,
+        keeper,
+        tip_bps,
+        set_at: clock.unix_timestamp,
+    });
+
+    msg!("🤝 Claim delegate set: keeper {} (tip {} bps)", keeper, tip_bps);
+    Ok(())
+}
+
+/// 🚫 Отозвать keeper'а (аккаунт закрывается, rent возвращается игроку)
+pub fn revoke_claim_delegate(ctx: Context<crate::RevokeClaimDelegate>) -> Result<()> {
+    emit!(crate::ClaimDelegateRevoked {
+        player: ctx.accounts.player_owner.key(),
+        keeper: ctx.accounts.claim_delegate.keeper,
+        revoked_at: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+/// 🤖 Claim всех бизнесов игрока keeper'ом (те же правила, что у claim_earnings)
+pub fn crank_claim<'info>(
+    ctx: Context<'_, '_, '_, 'info, crate::CrankClaim<'info>>,
+) -> Result<()> {
+    let fee_recipients = fee_recipient_accounts(&ctx.accounts.fee_split, ctx.remaining_accounts)?;
+    let current_time = Clock::get()?.unix_timestamp;
+    let accounts = &mut *ctx.accounts;
+
+    accounts.game_state.require_not_paused(PAUSE_CLAIMS)?;
+
+    require!(
+        accounts.player.can_claim_without_auto(current_time),
+        SolanaMafiaError::ClaimTooEarly
+    );
+    let claimable_amount = accounts.player.get_claimable_amount(current_time, &accounts.game_config)?;
+    if claimable_amount == 0 {
+        return Err(SolanaMafiaError::NoEarningsToClaim.into());
+    }
+
+    let tip_bps = accounts.claim_delegate.effective_tip_bps(accounts.game_config.max_keeper_tip_bps);
+    let treasury_info = accounts.treasury_pda.to_account_info();
+    settle_delegated_claim(
+        &mut PayoutAccounts {
+            game_state: &mut accounts.game_state,
+            game_config: &accounts.game_config,
+            treasury_pda: &treasury_info,
+            treasury_wallet: &accounts.treasury_wallet,
+            fee_split: &accounts.fee_split,
+            fee_recipients,
+        },
+        &mut accounts.player,
+        &accounts.player_owner,
+        &accounts.keeper.to_account_info(),
+        tip_bps,
+        claimable_amount,
+        current_time,
+    )
+}
+
+/// 🤖 Batch crank: claim для каждой тройки (player, claim_delegate, owner wallet).
+/// Игроки, которым еще рано клеймить или нечего клеймить, пропускаются.
+pub fn crank_claim_batch<'info>(
+    ctx: Context<'_, '_, '_, 'info, crate::CrankClaimBatch<'info>>,
+) -> Result<()> {
+    let fee_recipients = fee_recipient_accounts(&ctx.accounts.fee_split, ctx.remaining_accounts)?;
+    let players = &ctx.remaining_accounts[fee_recipients.len()..];
+    require!(
+        !players.is_empty() && players.len().is_multiple_of(3),
+        SolanaMafiaError::InvalidCrankBatch
+    );
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let accounts = &mut *ctx.accounts;
+    accounts.game_state.require_not_paused(PAUSE_CLAIMS)?;
+
+    let keeper = accounts.keeper.to_account_info();
+    let treasury_info = accounts.treasury_pda.to_account_info();
+    let mut payout_accounts = PayoutAccounts {
+        game_state: &mut accounts.game_state,
+        game_config: &accounts.game_config,
+        treasury_pda: &treasury_info,
+        treasury_wallet: &accounts.treasury_wallet,
+        fee_split: &accounts.fee_split,
+        fee_recipients,
+    };
+
+    let mut claimed = 0u32;
+    let mut skipped = 0u32;
+    for triple in players.chunks_exact(3) {
+        let (player_info, delegate_info, owner_info) = (&triple[0], &triple[1], &triple[2]);
+        let mut player = load_cranked_player(player_info)?;
+        let delegate = ClaimDelegate::load_checked(delegate_info, &player.owner, keeper.key)?;
+        require_keys_eq!(owner_info.key(), player.owner, SolanaMafiaError::InvalidCrankBatch);
+        require!(owner_info.is_writable, SolanaMafiaError::InvalidCrankBatch);
+
+        if !player.can_claim_without_auto(current_time) {
+            skipped += 1;
+            continue;
+        }
+        let claimable_amount = player.get_claimable_amount(current_time, payout_accounts.game_config)?;
+        if claimable_amount == 0 {
+            skipped += 1;
+            continue;
+        }
+
+        let tip_bps = delegate.effective_tip_bps(payout_accounts.game_config.max_keeper_tip_bps);
+        settle_delegated_claim(
+            &mut payout_accounts,
+            &mut player,
+            owner_info,
+            &keeper,
+            tip_bps,
+            claimable_amount,
+            current_time,
+        )?;
+        player.try_serialize(&mut &mut player_info.try_borrow_mut_data()?[..])?;
+        claimed += 1;
+    }
+
+    msg!("🤖 Crank batch: {} claimed, {} skipped", claimed, skipped);
+    Ok(())
+}
+
+/// Прочитать Player PDA из remaining accounts (владелец - программа, адрес - PDA своего owner)
+fn load_cranked_player(account: &AccountInfo) -> Result<PlayerCompact> {
+    require_keys_eq!(*account.owner, crate::ID, SolanaMafiaError::InvalidCrankBatch);
+    require!(account.is_writable, SolanaMafiaError::InvalidCrankBatch);
+
+    let player = {
+        let data = account.try_borrow_data()?;
+        PlayerCompact::try_deserialize(&mut &data[..])?
+    };
+    let expected = Pubkey::create_program_address(
+        &[PLAYER_SEED, player.owner.as_ref(), &[player.bump]],
+        &crate::ID,
+    ).map_err(|_| SolanaMafiaError::InvalidCrankBatch)?;
+    require_keys_eq!(account.key(), expected, SolanaMafiaError::InvalidCrankBatch);
+    Ok(player)
+}
+
+/// 💸 Выплата игроку + tip keeper'у, обновление claim времен и событие
+fn settle_delegated_claim<'info>(
+    payout_accounts: &mut PayoutAccounts<'_, 'info>,
+    player: &mut PlayerCompact,
+    owner_wallet: &AccountInfo<'info>,
+    keeper: &AccountInfo<'info>,
+    tip_bps: u16,
+    claimable_amount: u64,
+    current_time: i64,
+) -> Result<()> {
+    let payout = pay_out_earnings(
+        payout_accounts,
+        player,
+        owner_wallet,
+        claimable_amount,
+        None,
+        Some((keeper, tip_bps)),
+    )?;
+    player.process_claim(payout.paid_amount, current_time)?;
+
+    emit!(crate::DelegatedClaim {
+        player: player.owner,
+        keeper: keeper.key(),
+        amount: payout.paid_amount,
+        net_amount: payout.net_amount,
+        claim_fee: payout.claim_fee,
+        keeper_tip: payout.keeper_tip,
+        claimed_at: current_time,
+    });
+    Ok(())
+}
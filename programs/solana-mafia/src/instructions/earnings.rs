@@ -3,84 +3,403 @@ use anchor_lang::system_program;
 
 use crate::error::SolanaMafiaError;
 use crate::constants::*;
+use crate::instructions::fees::{fee_recipient_accounts, transfer_team_fee_from_pda};
+use crate::instructions::pricing::{check_max_payment, check_min_amount};
+use crate::instructions::solvency::plan_treasury_payout;
+use crate::state::{FeeSplit, GameConfig, GameState, PlayerCompact, Treasury};
 
 /// 🆕 Claim earnings with new individual business tracking system
-pub fn claim_earnings(ctx: Context<crate::ClaimEarnings>) -> Result<()> {
-    let player = &mut ctx.accounts.player;
-    let game_state = &mut ctx.accounts.game_state;
+pub fn claim_earnings<'info>(
+    ctx: Context<'_, '_, '_, 'info, crate::ClaimEarnings<'info>>,
+    min_claim: Option<u64>,
+) -> Result<()> {
+    let player = &ctx.accounts.player;
+    let game_config = &ctx.accounts.game_config;
+    let fee_recipients = fee_recipient_accounts(&ctx.accounts.fee_split, ctx.remaining_accounts)?;
     let clock = Clock::get()?;
     let current_time = clock.unix_timestamp;
     
+    ctx.accounts.game_state.require_not_paused(PAUSE_CLAIMS)?;
+    
     // 🚫 Проверка минимального интервала для пользователей без автонакоплений
-    if !player.auto_claim_purchased {
-        require!(
-            player.can_claim_without_auto(current_time),
-            SolanaMafiaError::ClaimTooEarly
-        );
-    }
+    require!(
+        player.can_claim_without_auto(current_time),
+        SolanaMafiaError::ClaimTooEarly
+    );
     
     // 💰 Рассчитываем earnings: без auto-claim = полная суточная доходность, с auto-claim = точный расчет по времени
-    let claimable_amount = player.get_claimable_amount(current_time)?;
+    let claimable_amount = player.get_claimable_amount(current_time, game_config)?;
     
     if claimable_amount == 0 {
         return Err(SolanaMafiaError::NoEarningsToClaim.into());
     }
     
-    // Calculate claim fee (2% от суммы claim)
-    let claim_fee = (claimable_amount as u128 * CLAIM_EARNINGS_FEE_PERCENT as u128 / 100) as u64;
-    let net_amount = claimable_amount.saturating_sub(claim_fee);
+    let accounts = &mut *ctx.accounts;
+    let treasury_info = accounts.treasury_pda.to_account_info();
+    let EarningsPayout { paid_amount, net_amount, claim_fee, .. } = pay_out_earnings(
+        &mut PayoutAccounts {
+            game_state: &mut accounts.game_state,
+            game_config: &accounts.game_config,
+            treasury_pda: &treasury_info,
+            treasury_wallet: &accounts.treasury_wallet,
+            fee_split: &accounts.fee_split,
+            fee_recipients,
+        },
+        &mut accounts.player,
+        &accounts.player_owner.to_account_info(),
+        claimable_amount,
+        min_claim,
+        None,
+    )?;
+    let player = &mut ctx.accounts.player;
     
-    // Check treasury has enough funds
-    let treasury_balance = ctx.accounts.treasury_pda.to_account_info().lamports();
-    if treasury_balance < claimable_amount {
-        return Err(ProgramError::InsufficientFunds.into());
+    // 🆕 Обновляем статистику игрока и времена claim всех бизнесов
+    player.process_claim(paid_amount, current_time)?;
+
+    emit!(crate::EarningsClaimed {
+        player: ctx.accounts.player_owner.key(),
+        amount: paid_amount,
+        claimed_at: current_time,
+    });
+    
+    msg!("💰 Claimed {} of {} lamports (net: {}, fee: {} [{}%]) [auto_claim: {}]", 
+         paid_amount, claimable_amount, net_amount, claim_fee, ctx.accounts.game_config.claim_fee_percent, player.has_auto_claim(current_time));
+    Ok(())
+}
+
+/// 🎯 Claim earnings of a single business; other businesses keep their timers
+pub fn claim_slot_earnings<'info>(
+    ctx: Context<'_, '_, '_, 'info, crate::ClaimEarnings<'info>>,
+    slot_index: u8,
+    min_claim: Option<u64>,
+) -> Result<()> {
+    let player = &ctx.accounts.player;
+    let game_config = &ctx.accounts.game_config;
+    let fee_recipients = fee_recipient_accounts(&ctx.accounts.fee_split, ctx.remaining_accounts)?;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    ctx.accounts.game_state.require_not_paused(PAUSE_CLAIMS)?;
+
+    if slot_index >= MAX_REGULAR_SLOTS {
+        return Err(SolanaMafiaError::InvalidSlotIndex.into());
+    }
+    let business = player.business_slots[slot_index as usize].business.as_ref()
+        .ok_or(SolanaMafiaError::BusinessNotFound)?;
+    if !business.is_active {
+        return Err(SolanaMafiaError::BusinessNotActive.into());
+    }
+
+    // 🚫 Без автонакоплений - 24 часа с последнего claim этого бизнеса
+    require!(
+        player.can_claim_slot_without_auto(slot_index as usize, current_time),
+        SolanaMafiaError::ClaimTooEarly
+    );
+
+    let claimable_amount = player.calculate_slot_claimable_earnings(slot_index as usize, current_time, game_config);
+    if claimable_amount == 0 {
+        return Err(SolanaMafiaError::NoEarningsToClaim.into());
     }
+
+    let accounts = &mut *ctx.accounts;
+    let treasury_info = accounts.treasury_pda.to_account_info();
+    let EarningsPayout { paid_amount, net_amount, claim_fee, .. } = pay_out_earnings(
+        &mut PayoutAccounts {
+            game_state: &mut accounts.game_state,
+            game_config: &accounts.game_config,
+            treasury_pda: &treasury_info,
+            treasury_wallet: &accounts.treasury_wallet,
+            fee_split: &accounts.fee_split,
+            fee_recipients,
+        },
+        &mut accounts.player,
+        &accounts.player_owner.to_account_info(),
+        claimable_amount,
+        min_claim,
+        None,
+    )?;
+
+    ctx.accounts.player.process_slot_claim(slot_index as usize, paid_amount, current_time)?;
+
+    emit!(crate::SlotEarningsClaimed {
+        player: ctx.accounts.player_owner.key(),
+        slot_index,
+        amount: paid_amount,
+        net_amount,
+        claim_fee,
+        claimed_at: current_time,
+    });
+
+    Ok(())
+}
+
+/// 🏦 Аккаунты treasury для выплаты earnings (общие для claim и crank_claim)
+pub struct PayoutAccounts<'a, 'info> {
+    pub game_state: &'a mut GameState,
+    pub game_config: &'a GameConfig,
+    pub treasury_pda: &'a AccountInfo<'info>,
+    pub treasury_wallet: &'a AccountInfo<'info>,
+    pub fee_split: &'a FeeSplit,
+    pub fee_recipients: &'a [AccountInfo<'info>],
+}
+
+/// Результат выплаты earnings
+pub struct EarningsPayout {
+    /// Списано из treasury (claim fee и tip включены)
+    pub paid_amount: u64,
+    /// Получено кошельком игрока
+    pub net_amount: u64,
+    pub claim_fee: u64,
+    pub keeper_tip: u64,
+}
+
+/// 💸 Выплата earnings из treasury (общая для claim_earnings, claim_slot_earnings и crank_claim):
+/// solvency guard, claim fee команде, min_claim, tip keeper'а с чистой суммы.
+pub fn pay_out_earnings<'info>(
+    accounts: &mut PayoutAccounts<'_, 'info>,
+    player: &mut PlayerCompact,
+    recipient: &AccountInfo<'info>,
+    claimable_amount: u64,
+    min_claim: Option<u64>,
+    keeper_tip: Option<(&AccountInfo<'info>, u16)>,
+) -> Result<EarningsPayout> {
+    let treasury_info = accounts.treasury_pda;
+
+    // 🏦 Не опускаем treasury ниже rent-exempt минимума, остаток - в долг игроку
+    let paid_amount = plan_treasury_payout(
+        accounts.game_state,
+        accounts.game_config,
+        player,
+        treasury_info,
+        claimable_amount,
+        accounts.game_config.claim_fee_percent,
+    )?;
+    
+    // Calculate claim fee (claim_fee_percent от выплаченной суммы)
+    let claim_fee = accounts.game_config.calculate_claim_fee(paid_amount);
+    let after_fee = paid_amount.saturating_sub(claim_fee);
+
+    // 🤖 Tip keeper'а берется из доли игрока
+    let keeper_tip_amount = match keeper_tip {
+        Some((_, tip_bps)) => (after_fee as u128 * tip_bps as u128 / 10_000) as u64,
+        None => 0,
+    };
+    let net_amount = after_fee - keeper_tip_amount;
+    check_min_amount(net_amount, min_claim, SolanaMafiaError::MinClaimNotMet)?;
     
     // Transfer earnings from treasury PDA to player using manual lamports manipulation
     if net_amount > 0 {
-        **ctx.accounts.treasury_pda.to_account_info().try_borrow_mut_lamports()? -= net_amount;
-        **ctx.accounts.player_owner.to_account_info().try_borrow_mut_lamports()? += net_amount;
+        **treasury_info.try_borrow_mut_lamports()? -= net_amount;
+        **recipient.try_borrow_mut_lamports()? += net_amount;
         msg!("💰 Transferred {} lamports earnings to player", net_amount);
     }
+
+    if let Some((keeper, _)) = keeper_tip {
+        if keeper_tip_amount > 0 {
+            **treasury_info.try_borrow_mut_lamports()? -= keeper_tip_amount;
+            **keeper.try_borrow_mut_lamports()? += keeper_tip_amount;
+        }
+    }
     
     // Transfer claim fee from treasury PDA to admins using manual lamports manipulation
     if claim_fee > 0 {
-        **ctx.accounts.treasury_pda.to_account_info().try_borrow_mut_lamports()? -= claim_fee;
-        **ctx.accounts.treasury_wallet.to_account_info().try_borrow_mut_lamports()? += claim_fee;
+        transfer_team_fee_from_pda(
+            treasury_info,
+            accounts.treasury_wallet,
+            accounts.fee_split,
+            accounts.fee_recipients,
+            claim_fee,
+        )?;
         msg!("💳 Claim fee {} lamports sent to admins", claim_fee);
     }
     
-    // 🆕 Обновляем статистику игрока и времена claim всех бизнесов
-    player.process_claim(claimable_amount, current_time)?;
-    
     // Update game statistics
-    game_state.add_withdrawal(claimable_amount);
+    accounts.game_state.add_withdrawal(paid_amount);
 
-    emit!(crate::EarningsClaimed {
+    Ok(EarningsPayout {
+        paid_amount,
+        net_amount,
+        claim_fee,
+        keeper_tip: keeper_tip_amount,
+    })
+}
+
+/// 🔁 Reinvest claimable earnings into a business without moving lamports out of the treasury.
+/// fund_upgrade = true: сначала оплачивается следующий уровень, остаток идет в депозит.
+/// Комиссия reinvest_fee_percent остается в treasury как резерв.
+pub fn reinvest_earnings(
+    ctx: Context<crate::ReinvestEarnings>,
+    slot_index: u8,
+    fund_upgrade: bool,
+    min_reinvested: Option<u64>,
+) -> Result<()> {
+    let player = &mut ctx.accounts.player;
+    let game_state = &mut ctx.accounts.game_state;
+    let game_config = &ctx.accounts.game_config;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    game_state.require_not_paused(PAUSE_CLAIMS)?;
+    if fund_upgrade {
+        game_state.require_not_paused(PAUSE_UPGRADES)?;
+    }
+
+    // 🚫 Тот же интервал, что и у claim_earnings
+    require!(
+        player.can_claim_without_auto(current_time),
+        SolanaMafiaError::ClaimTooEarly
+    );
+
+    if slot_index >= MAX_REGULAR_SLOTS {
+        return Err(SolanaMafiaError::InvalidSlotIndex.into());
+    }
+
+    let claimable_amount = player.get_claimable_amount(current_time, game_config)?;
+    if claimable_amount == 0 {
+        return Err(SolanaMafiaError::NoEarningsToClaim.into());
+    }
+
+    let reinvest_fee = claimable_amount
+        .checked_mul(game_config.reinvest_fee_percent as u64)
+        .ok_or(SolanaMafiaError::MathOverflow)?
+        .checked_div(100)
+        .ok_or(SolanaMafiaError::MathOverflow)?;
+    let reinvested_amount = claimable_amount
+        .checked_sub(reinvest_fee)
+        .ok_or(SolanaMafiaError::MathOverflow)?;
+    check_min_amount(reinvested_amount, min_reinvested, SolanaMafiaError::MinReinvestNotMet)?;
+
+    // Earnings засчитаны как заработанные (без удержанной комиссии),
+    // времена claim сброшены - до изменения ставки/депозита
+    player.process_claim(reinvested_amount, current_time)?;
+
+    let business = player.business_slots[slot_index as usize].business.as_mut()
+        .ok_or(SolanaMafiaError::BusinessNotFound)?;
+    if !business.is_active {
+        return Err(SolanaMafiaError::BusinessNotActive.into());
+    }
+
+    // ⬆️ Апгрейд за счет earnings
+    let mut upgrade_cost = 0u64;
+    if fund_upgrade {
+        let next_level = business.upgrade_level + 1;
+        if next_level > game_config.max_upgrade_level {
+            return Err(SolanaMafiaError::MaxLevelReached.into());
+        }
+        upgrade_cost = business.get_upgrade_cost(next_level, game_config)?;
+        if reinvested_amount < upgrade_cost {
+            return Err(SolanaMafiaError::InsufficientFundsForUpgrade.into());
+        }
+        // Team fee не взимается - вся стоимость остается в treasury
+        business.upgrade_to_level(next_level, upgrade_cost, upgrade_cost, game_config)?;
+    }
+
+    // Остаток - в депозит (в пределах max_deposit типа)
+    let principal_added = reinvested_amount - upgrade_cost;
+    if principal_added > 0 {
+        require!(
+            business.base_invested_amount.saturating_add(principal_added)
+                <= game_config.get_max_deposit(business.business_type.to_index()),
+            SolanaMafiaError::DepositAboveMax
+        );
+        business.add_principal(principal_added)?;
+    }
+
+    let new_level = business.upgrade_level;
+    let new_total_invested = business.total_invested_amount;
+    let new_daily_rate = business.daily_rate;
+
+    player.total_invested = player.total_invested
+        .checked_add(reinvested_amount)
+        .ok_or(SolanaMafiaError::MathOverflow)?;
+    player.total_upgrade_spent = player.total_upgrade_spent.saturating_add(upgrade_cost);
+
+    game_state.add_investment(reinvested_amount);
+
+    emit!(crate::EarningsReinvested {
         player: ctx.accounts.player_owner.key(),
-        amount: claimable_amount,
-        claimed_at: current_time,
+        slot_index,
+        claimed_amount: claimable_amount,
+        reinvest_fee,
+        reinvested_amount,
+        upgrade_cost,
+        new_level,
+        new_total_invested,
+        new_daily_rate,
+        reinvested_at: current_time,
     });
-    
-    msg!("💰 Claimed {} lamports (net: {}, fee: {} [{}%]) [auto_claim: {}]", 
-         claimable_amount, net_amount, claim_fee, CLAIM_EARNINGS_FEE_PERCENT, player.auto_claim_purchased);
+
+    Ok(())
+}
+
+/// 🧾 Redeem owed earnings for the player at the head of the debt queue (anyone can call)
+pub fn redeem_owed(ctx: Context<crate::RedeemOwed>) -> Result<()> {
+    let player = &mut ctx.accounts.player;
+    let game_state = &mut ctx.accounts.game_state;
+    let clock = Clock::get()?;
+
+    game_state.require_not_paused(PAUSE_CLAIMS)?;
+
+    if !player.has_debt() {
+        return Err(SolanaMafiaError::NoOwedEarnings.into());
+    }
+    if player.debt_ticket != game_state.debt_queue_head {
+        return Err(SolanaMafiaError::NotFirstInDebtQueue.into());
+    }
+
+    // Погашаем сколько позволяют свободные резервы (treasury не ниже rent-exempt)
+    let available = Treasury::available_balance(&ctx.accounts.treasury_pda.to_account_info())?;
+    let amount = player.owed_earnings.min(available);
+    if amount == 0 {
+        return Err(SolanaMafiaError::TreasuryReservesEmpty.into());
+    }
+
+    **ctx.accounts.treasury_pda.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.player_owner.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    let ticket = player.debt_ticket;
+    let ticket_closed = player.repay_owed_earnings(amount)?;
+    let was_pro_rata = game_state.pro_rata_mode;
+    game_state.repay_debt(amount, ticket_closed)?;
+    game_state.add_withdrawal(amount);
+    player.total_earned = player.total_earned.saturating_add(amount);
+
+    emit!(crate::OwedRedeemed {
+        player: player.owner,
+        ticket,
+        amount,
+        remaining: player.owed_earnings,
+        total_owed: game_state.total_owed,
+        redeemed_at: clock.unix_timestamp,
+    });
+
+    if was_pro_rata && !game_state.pro_rata_mode {
+        emit!(crate::SolvencyModeChanged {
+            pro_rata_mode: false,
+            available_reserves: available - amount,
+            total_owed: game_state.total_owed,
+            changed_at: clock.unix_timestamp,
+        });
+    }
+
+    msg!("🧾 Redeemed {} lamports for ticket #{} (remaining: {})", amount, ticket, player.owed_earnings);
     Ok(())
 }
 
 /// 🆕 Купить автонакопления за 0.05 SOL (разовая покупка на всю игру)
-pub fn purchase_auto_claim(ctx: Context<crate::PurchaseAutoClaim>) -> Result<()> {
+pub fn purchase_auto_claim(
+    ctx: Context<crate::PurchaseAutoClaim>,
+    tier: u8,
+    max_total_payment: Option<u64>,
+) -> Result<()> {
     let player = &mut ctx.accounts.player;
     let game_state = &mut ctx.accounts.game_state;
+    let game_config = &ctx.accounts.game_config;
     let clock = Clock::get()?;
     
-    // Проверяем что автонакопления еще не куплены
-    require!(
-        !player.auto_claim_purchased,
-        SolanaMafiaError::AutoClaimAlreadyPurchased
-    );
-    
-    // Переводим 0.05 SOL с игрока в treasury PDA
-    let auto_claim_cost = AUTO_CLAIM_COST;
+    // Переводим стоимость тарифа с игрока в treasury PDA
+    let (auto_claim_cost, days) = game_config.get_auto_claim_tier(tier)
+        .ok_or(SolanaMafiaError::InvalidAutoClaimTier)?;
+    check_max_payment(auto_claim_cost, max_total_payment)?;
     
     // Transfer from player to treasury PDA
     let transfer_instruction = system_program::Transfer {
@@ -95,8 +414,8 @@ pub fn purchase_auto_claim(ctx: Context<crate::PurchaseAutoClaim>) -> Result<()>
     
     system_program::transfer(cpi_context, auto_claim_cost)?;
     
-    // Активируем автонакопления для игрока
-    player.purchase_auto_claim()?;
+    // Активируем / продлеваем подписку
+    let expires_at = player.purchase_auto_claim(days, clock.unix_timestamp, game_config)?;
     
     // Обновляем статистику treasury
     game_state.add_investment(auto_claim_cost);
@@ -104,28 +423,30 @@ pub fn purchase_auto_claim(ctx: Context<crate::PurchaseAutoClaim>) -> Result<()>
     // Эмитим event для отслеживания
     emit!(crate::AutoClaimPurchased {
         player: player.owner,
+        tier,
+        days,
         cost: auto_claim_cost,
+        expires_at,
         purchased_at: clock.unix_timestamp,
     });
     
-    msg!("🚀 Auto claim purchased for {} lamports by player {}", 
-         auto_claim_cost, player.owner);
+    msg!("🚀 Auto claim ({} days) purchased for {} lamports by player {}, expires at {}", 
+         days, auto_claim_cost, player.owner, expires_at);
     
     Ok(())
 }
 
-/// 🆕 Получить глобальную статистику (оставлено для совместимости)
-pub fn get_global_stats(ctx: Context<crate::GetGlobalStats>) -> Result<()> {
+/// 🆕 Получить глобальную статистику (return data)
+pub fn get_global_stats(ctx: Context<crate::GetGlobalStats>) -> Result<crate::GlobalStats> {
     let game_state = &ctx.accounts.game_state;
-    
-    // Логируем статистику
-    msg!("GLOBAL_STATS: players={}, invested={}, withdrawn={}, businesses={}, treasury={}", 
-         game_state.total_players,
-         game_state.total_invested,
-         game_state.total_withdrawn,
-         game_state.total_businesses,
-         game_state.total_treasury_collected
-    );
-    
-    Ok(())
-}
\ No newline at end of file
+
+    Ok(crate::GlobalStats {
+        total_players: game_state.total_players,
+        total_invested: game_state.total_invested,
+        total_withdrawn: game_state.total_withdrawn,
+        total_businesses: game_state.total_businesses,
+        total_treasury_collected: game_state.total_treasury_collected,
+        total_owed: game_state.total_owed,
+        pro_rata_mode: game_state.pro_rata_mode,
+    })
+}
@@ -0,0 +1,98 @@
+// 💸 Распределение командных комиссий (FeeSplit или treasury_wallet)
+// Аккаунты получателей передаются первыми в remaining accounts, в порядке FeeSplit.recipients
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::state::*;
+use crate::error::SolanaMafiaError;
+
+/// Провалидировать аккаунты получателей из remaining accounts против FeeSplit
+pub fn fee_recipient_accounts<'a, 'info>(
+    fee_split: &FeeSplit,
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Result<&'a [AccountInfo<'info>]> {
+    let count = fee_split.recipient_count as usize;
+    require!(
+        remaining_accounts.len() >= count,
+        SolanaMafiaError::InvalidFeeRecipient
+    );
+
+    let accounts = &remaining_accounts[..count];
+    for (account, recipient) in accounts.iter().zip(fee_split.active_recipients()) {
+        require_keys_eq!(account.key(), recipient.wallet, SolanaMafiaError::InvalidFeeRecipient);
+        require!(account.is_writable, SolanaMafiaError::InvalidFeeRecipient);
+    }
+
+    Ok(accounts)
+}
+
+/// Перевести командную комиссию с кошелька игрока (CPI System Program)
+pub fn transfer_team_fee<'info>(
+    system_program: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    treasury_wallet: &AccountInfo<'info>,
+    fee_split: &FeeSplit,
+    recipient_accounts: &[AccountInfo<'info>],
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let transfer = |to: &AccountInfo<'info>, lamports: u64| -> Result<()> {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: to.clone(),
+                },
+            ),
+            lamports,
+        )
+    };
+
+    if !fee_split.is_active() {
+        return transfer(treasury_wallet, amount);
+    }
+
+    for (account, share) in recipient_accounts.iter().zip(fee_split.split_amount(amount)) {
+        if share > 0 {
+            transfer(account, share)?;
+        }
+    }
+    Ok(())
+}
+
+/// Перевести командную комиссию из treasury PDA (прямое изменение lamports)
+pub fn transfer_team_fee_from_pda<'info>(
+    treasury_pda: &AccountInfo<'info>,
+    treasury_wallet: &AccountInfo<'info>,
+    fee_split: &FeeSplit,
+    recipient_accounts: &[AccountInfo<'info>],
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let move_lamports = |to: &AccountInfo<'info>, lamports: u64| -> Result<()> {
+        **treasury_pda.try_borrow_mut_lamports()? = treasury_pda.lamports()
+            .checked_sub(lamports)
+            .ok_or(ProgramError::InsufficientFunds)?;
+        **to.try_borrow_mut_lamports()? += lamports;
+        Ok(())
+    };
+
+    if !fee_split.is_active() {
+        return move_lamports(treasury_wallet, amount);
+    }
+
+    for (account, share) in recipient_accounts.iter().zip(fee_split.split_amount(amount)) {
+        if share > 0 {
+            move_lamports(account, share)?;
+        }
+    }
+    Ok(())
+}
@@ -1,11 +1,23 @@
 pub mod admin;
 pub mod business;
+pub mod crank;
 pub mod earnings;
+pub mod fees;
 pub mod player;
+pub mod pricing;
+pub mod quotes;
+pub mod referral;
 pub mod slots;
+pub mod solvency;
 
 pub use admin::*;
 pub use business::*;
+pub use crank::*;
 pub use earnings::*;
+pub use fees::*;
 pub use player::*;
-pub use slots::*;
\ No newline at end of file
+pub use pricing::*;
+pub use quotes::*;
+pub use referral::*;
+pub use slots::*;
+pub use solvency::*;
\ No newline at end of file
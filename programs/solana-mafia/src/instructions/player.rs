@@ -1,18 +1,26 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
 
 use crate::state::*;
+use crate::constants::*;
 use crate::error::SolanaMafiaError;
+use crate::instructions::fees::{fee_recipient_accounts, transfer_team_fee};
+use crate::instructions::pricing::check_max_payment;
+use crate::instructions::referral::{pay_referral_bonuses, validate_new_referrer};
 // Импорты контекстов убраны - используем прямо через lib.rs
 
-/// Create new player (with entry fee)
-pub fn create_player(ctx: Context<crate::CreatePlayer>) -> Result<()> {
+/// Create new player (with entry fee and optional referrer)
+pub fn create_player<'info>(
+    ctx: Context<'_, '_, '_, 'info, crate::CreatePlayer<'info>>,
+    referrer: Option<Pubkey>,
+    max_total_payment: Option<u64>,
+) -> Result<()> {
     let game_config = &ctx.accounts.game_config;
     let game_state = &mut ctx.accounts.game_state;
     let player = &mut ctx.accounts.player;
     let clock = Clock::get()?;
     
     // 🔒 УБРАЛИ ПРОВЕРКУ is_paused - игра всегда активна!
+    require!(game_config.registrations_open, SolanaMafiaError::RegistrationsClosed);
     
     // 🔒 БЕЗОПАСНОСТЬ: Проверяем что treasury_wallet соответствует game_state
     if ctx.accounts.treasury_wallet.key() != game_state.treasury_wallet {
@@ -22,17 +30,30 @@ pub fn create_player(ctx: Context<crate::CreatePlayer>) -> Result<()> {
     // Get current dynamic entry fee based on total players
     let current_total_players = game_state.total_players;
     let entry_fee = game_config.get_current_entry_fee(current_total_players);
+    check_max_payment(entry_fee, max_total_payment)?;
     
-    system_program::transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.owner.to_account_info(),
-                to: ctx.accounts.treasury_wallet.to_account_info(),
-            },
-        ),
+    let fee_recipients = fee_recipient_accounts(&ctx.accounts.fee_split, ctx.remaining_accounts)?;
+    let referrer_accounts = &ctx.remaining_accounts[fee_recipients.len()..];
+    let referrer = validate_new_referrer(&ctx.accounts.owner.key(), referrer, referrer_accounts)?;
+
+    // 🤝 Реферальные бонусы из entry fee, остаток - команде
+    let team_fee = pay_referral_bonuses(
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner.key(),
+        referrer,
+        referrer_accounts,
+        game_config,
         entry_fee,
     )?;
+    transfer_team_fee(
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.treasury_wallet.to_account_info(),
+        &ctx.accounts.fee_split,
+        fee_recipients,
+        team_fee,
+    )?;
 
     // 🔒 ПРОВЕРКА: Player уже существует?
     if player.owner != Pubkey::default() {
@@ -46,6 +67,7 @@ pub fn create_player(ctx: Context<crate::CreatePlayer>) -> Result<()> {
         clock.unix_timestamp,
     );
     player.set_has_paid_entry(true);
+    player.referrer = referrer;
     
     // Update game stats
     game_state.add_player();
@@ -58,6 +80,7 @@ pub fn create_player(ctx: Context<crate::CreatePlayer>) -> Result<()> {
     emit!(crate::PlayerCreated {
         wallet: ctx.accounts.owner.key(),
         entry_fee,
+        referrer,
         created_at: clock.unix_timestamp,
     });
     Ok(())
@@ -76,57 +99,71 @@ pub fn health_check_player(ctx: Context<crate::HealthCheckPlayer>) -> Result<()>
 }
 
 /// 🆕 Получить данные игрока для фронтенда (с новой системой индивидуальных claim)
-pub fn get_player_data(ctx: Context<crate::GetPlayerData>) -> Result<()> {
+pub fn get_player_data(ctx: Context<crate::GetPlayerData>) -> Result<crate::PlayerFrontendData> {
     let player = &ctx.accounts.player;
+    let game_config = &ctx.accounts.game_config;
     let clock = Clock::get()?;
-    let current_time = clock.unix_timestamp;
 
-    // 🆕 Получаем структурированные данные для фронтенда
-    let frontend_data = player.get_frontend_data(current_time);
-    
-    // Логируем данные в новом формате (фронтенд может парсить это)
-    msg!("PLAYER_FRONTEND_DATA: wallet={}, total_invested={}, total_earned={}, claimable_earnings={}, businesses_count={}, active_businesses={}, auto_claim_purchased={}, can_claim={}", 
-         frontend_data.wallet,
-         frontend_data.total_invested,
-         frontend_data.total_earned,
-         frontend_data.claimable_earnings,
-         frontend_data.businesses_count,
-         frontend_data.active_businesses,
-         frontend_data.auto_claim_purchased,
-         frontend_data.can_claim
-    );
-    
-    Ok(())
+    Ok(player.get_frontend_data(clock.unix_timestamp, game_config))
 }
 
-/// 🆕 Получить только валидные (принадлежащие) бизнесы игрока
-pub fn get_valid_player_businesses(ctx: Context<crate::GetValidPlayerBusinesses>) -> Result<()> {
+/// 🆕 Получить все слоты игрока (с бизнесами и пустые)
+pub fn get_valid_player_businesses(ctx: Context<crate::GetValidPlayerBusinesses>) -> Result<Vec<crate::PlayerSlotData>> {
     let player = &ctx.accounts.player;
-    let all_businesses = player.get_all_businesses();
-    
-    msg!("VALID_BUSINESSES: player={}, total_slots={}, active_businesses={}", 
-         player.owner, 
-         player.business_slots.len(), 
-         all_businesses.len()
-    );
-    
-    // Логируем детали каждого слота
-    for (index, slot) in player.business_slots.iter().enumerate() {
-        if let Some(business) = &slot.business {
-            msg!("SLOT_{}: type={}, unlocked={}, slot_type={:?}, business_type={}, invested={}, active={}", 
-                 index,
-                 slot.slot_type() as u8,
-                 slot.is_unlocked(),
-                 slot.slot_type(),
-                 business.business_type.to_index(),
-                 business.total_invested_amount,
-                 business.is_active
-            );
-        } else {
-            msg!("SLOT_{}: type={:?}, unlocked={}, empty", 
-                 index, slot.slot_type(), slot.is_unlocked());
-        }
-    }
-    
+
+    let slots = player.business_slots.iter()
+        .enumerate()
+        .map(|(index, slot)| {
+            let mut data = crate::PlayerSlotData {
+                slot_index: index as u8,
+                slot_type: slot.slot_type() as u8,
+                is_unlocked: slot.is_unlocked(),
+                is_paid: slot.is_paid(),
+                has_business: slot.business.is_some(),
+                business_type: 0,
+                upgrade_level: 0,
+                base_invested_amount: 0,
+                total_invested_amount: 0,
+                daily_rate: 0,
+                is_active: false,
+                purchased_at: 0,
+                last_claim_at: 0,
+            };
+            if let Some(business) = &slot.business {
+                data.business_type = business.business_type.to_index() as u8;
+                data.upgrade_level = business.upgrade_level;
+                data.base_invested_amount = business.base_invested_amount;
+                data.total_invested_amount = business.total_invested_amount;
+                data.daily_rate = business.daily_rate;
+                data.is_active = business.is_active;
+                data.purchased_at = business.purchased_at;
+                data.last_claim_at = business.last_claim_at.unwrap_or(0);
+            }
+            data
+        })
+        .collect();
+
+    Ok(slots)
+}
+
+/// 🤝 Claim accumulated referral bonuses from the player's PDA
+pub fn claim_referral_bonus(ctx: Context<crate::ClaimReferralBonus>) -> Result<()> {
+    let player = &mut ctx.accounts.player;
+    ctx.accounts.game_state.require_not_paused(PAUSE_CLAIMS)?;
+
+    let amount = player.claim_referral_bonus()?;
+
+    // Бонусы лежат на Player PDA сверх rent-exempt минимума
+    **player.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.player_owner.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    emit!(crate::ReferralBonusClaimed {
+        referrer: player.owner,
+        amount,
+        total_referral_earned: player.total_referral_earned,
+        claimed_at: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("🤝 Referral bonus claimed: {} lamports", amount);
     Ok(())
-}
\ No newline at end of file
+}
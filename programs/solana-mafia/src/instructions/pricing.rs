@@ -0,0 +1,404 @@
+// 🧮 Чистые расчеты цен create/upgrade/sell.
+// Используются и реальными инструкциями, и quote_* - UI не расходится с on-chain математикой.
+
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::constants::*;
+use crate::error::SolanaMafiaError;
+
+/// Расчет покупки бизнеса в слот
+pub struct CreatePricing {
+    pub business_type: BusinessType,
+    pub base_cost: u64,
+    pub daily_rate: u16,
+    pub upgrade_costs: [u64; MAX_UPGRADE_LEVEL as usize],
+    pub slot_cost: u64,
+    pub team_fee: u64,
+    pub treasury_amount: u64,
+    /// Депозит + стоимость слота (без entry fee)
+    pub total_payment: u64,
+}
+
+/// Расчет улучшения бизнеса на следующий уровень
+pub struct UpgradePricing {
+    pub next_level: u8,
+    pub upgrade_cost: u64,
+    pub team_fee: u64,
+    pub treasury_amount: u64,
+    pub new_daily_rate: u16,
+}
+
+/// Расчет продажи бизнеса (до учета платежеспособности treasury)
+pub struct SellPricing {
+    pub business_type: u8,
+    pub days_held: u64,
+    pub base_fee_percent: u8,
+    pub slot_discount: u8,
+    pub final_fee_percent: u8,
+    /// Продаваемая часть вложений (весь total_invested_amount при полной продаже)
+    pub total_invested: u64,
+    /// Вложения, остающиеся в бизнесе (0 при полной продаже)
+    pub remaining_invested: u64,
+    pub sell_fee: u64,
+    pub refund_amount: u64,
+}
+
+/// Расчет снятия верхнего уровня улучшения (до учета платежеспособности treasury)
+pub struct DowngradePricing {
+    pub old_level: u8,
+    pub upgrade_cost: u64,
+    pub rate_bonus: u16,
+    pub days_held: u64,
+    pub base_fee_percent: u8,
+    pub slot_discount: u8,
+    pub final_fee_percent: u8,
+    pub downgrade_fee: u64,
+    pub refund_amount: u64,
+}
+
+/// Комиссия ранней продажи со скидкой слота: (days_held, base, slot_discount, final)
+fn early_sell_fee(slot: &BusinessSlotCompact, business: &Business, config: &GameConfig, current_time: i64) -> (u64, u8, u8, u8) {
+    let days_held = ((current_time - business.created_at) / 86400) as u64;
+    let base_fee_percent = config.get_sell_fee_percent(days_held);
+    let slot_discount = slot.get_sell_fee_discount(config);
+    (days_held, base_fee_percent, slot_discount, base_fee_percent.saturating_sub(slot_discount))
+}
+
+/// 🏪 Цена create_business (target_level = None) / create_business_with_level (Some).
+/// with_level: депозит должен ровно равняться каталожной цене + апгрейды.
+/// create_business: в strict_pricing режиме депозит должен совпадать с разрешенным тиром.
+pub fn price_create_business(
+    player: &PlayerCompact,
+    config: &GameConfig,
+    business_type: u8,
+    deposit_amount: u64,
+    slot_index: u8,
+    target_level: Option<u8>,
+) -> Result<CreatePricing> {
+    let level = target_level.unwrap_or(0);
+    if level > config.max_upgrade_level {
+        return Err(SolanaMafiaError::MaxLevelReached.into());
+    }
+
+    if slot_index >= MAX_REGULAR_SLOTS {
+        return Err(SolanaMafiaError::InvalidSlotIndex.into());
+    }
+
+    // Все слоты разблокированы по умолчанию - проверяем только занятость
+    let slot = &player.business_slots[slot_index as usize];
+    if slot.business.is_some() {
+        return Err(SolanaMafiaError::SlotAlreadyOccupied.into());
+    }
+
+    if (business_type as usize) >= BUSINESS_TYPES_COUNT {
+        return Err(SolanaMafiaError::InvalidBusinessType.into());
+    }
+
+    let business_type = BusinessType::from_index(business_type).unwrap();
+    let base_cost = business_type.get_base_cost(config);
+    let daily_rate = business_type.get_base_rate(config);
+
+    if deposit_amount < base_cost {
+        return Err(SolanaMafiaError::InsufficientDeposit.into());
+    }
+
+    // Стоимость апгрейдов для create_business_with_level
+    let mut upgrade_costs = [0u64; MAX_UPGRADE_LEVEL as usize];
+    for level in 1..=level {
+        upgrade_costs[(level - 1) as usize] = config
+            .get_upgrade_cost(base_cost, level)
+            .ok_or(SolanaMafiaError::MathOverflow)?;
+    }
+
+    // 🏷️ Депозит = ровно то, что будет записано в Business как вложения
+    if target_level.is_some() {
+        let expected_total_cost = upgrade_costs.iter()
+            .try_fold(base_cost, |total, &cost| total.checked_add(cost))
+            .ok_or(SolanaMafiaError::MathOverflow)?;
+        require!(deposit_amount == expected_total_cost, SolanaMafiaError::UpgradeCostMismatch);
+    } else {
+        require!(
+            deposit_amount <= config.get_max_deposit(business_type.to_index()),
+            SolanaMafiaError::DepositAboveMax
+        );
+        require!(config.is_allowed_deposit(base_cost, deposit_amount), SolanaMafiaError::DepositNotAllowed);
+    }
+
+    // slot_cost - невозвратный донат команде, НЕ входит в стоимость бизнеса
+    let slot_cost = slot.get_slot_cost(deposit_amount, config);
+    let total_payment = deposit_amount
+        .checked_add(slot_cost)
+        .ok_or(SolanaMafiaError::MathOverflow)?;
+
+    // treasury_fee_percent команде, остаток в treasury PDA (только с депозита)
+    let (team_fee, treasury_amount) = config.split_team_fee(deposit_amount)?;
+
+    Ok(CreatePricing {
+        business_type,
+        base_cost,
+        daily_rate,
+        upgrade_costs,
+        slot_cost,
+        team_fee,
+        treasury_amount,
+        total_payment,
+    })
+}
+
+/// ⬆️ Цена upgrade_business
+pub fn price_upgrade_business(
+    player: &PlayerCompact,
+    config: &GameConfig,
+    slot_index: u8,
+) -> Result<UpgradePricing> {
+    if slot_index >= MAX_REGULAR_SLOTS {
+        return Err(SolanaMafiaError::InvalidSlotIndex.into());
+    }
+
+    let business = player.business_slots[slot_index as usize].business.as_ref()
+        .ok_or(SolanaMafiaError::BusinessNotFound)?;
+
+    if !business.is_active {
+        return Err(SolanaMafiaError::BusinessNotActive.into());
+    }
+
+    let next_level = business.upgrade_level + 1;
+    if next_level > config.max_upgrade_level {
+        return Err(SolanaMafiaError::MaxLevelReached.into());
+    }
+
+    let upgrade_cost = business.get_upgrade_cost(next_level, config)?;
+
+    // Upgrade делится как депозит: team fee команде, остаток в treasury PDA
+    let (team_fee, treasury_amount) = config.split_team_fee(upgrade_cost)?;
+
+    let mut upgraded = *business;
+    upgraded.upgrade_to_level(next_level, upgrade_cost, treasury_amount, config)?;
+
+    Ok(UpgradePricing {
+        next_level,
+        upgrade_cost,
+        team_fee,
+        treasury_amount,
+        new_daily_rate: upgraded.daily_rate,
+    })
+}
+
+/// 🔥 Цена sell_business (amount = None) / sell_business_partial (Some).
+/// Частичная продажа выводит только базовый депозит сверх минимального депозита типа.
+pub fn price_sell_business(
+    player: &PlayerCompact,
+    config: &GameConfig,
+    slot_index: u8,
+    amount: Option<u64>,
+    current_time: i64,
+) -> Result<SellPricing> {
+    if slot_index >= MAX_REGULAR_SLOTS {
+        return Err(SolanaMafiaError::InvalidSlotIndex.into());
+    }
+
+    let slot = &player.business_slots[slot_index as usize];
+    let business = slot.business.as_ref()
+        .ok_or(SolanaMafiaError::BusinessNotFound)?;
+
+    if !business.is_active {
+        return Err(SolanaMafiaError::BusinessNotActive.into());
+    }
+
+    // Комиссия ранней продажи со скидкой слота
+    let (days_held, base_fee_percent, slot_discount, final_fee_percent) =
+        early_sell_fee(slot, business, config, current_time);
+
+    let (total_invested, remaining_invested) = match amount {
+        None => (business.get_total_investment_for_refund(), 0),
+        Some(amount) => {
+            let remaining_base = business.base_invested_amount.saturating_sub(amount);
+            require!(
+                amount > 0
+                    && amount <= business.base_invested_amount
+                    && remaining_base >= config.get_min_deposit(business.business_type.to_index()),
+                SolanaMafiaError::PartialSellBelowMinimum
+            );
+            (amount, business.total_invested_amount - amount)
+        }
+    };
+    let sell_fee = total_invested
+        .checked_mul(final_fee_percent as u64)
+        .ok_or(SolanaMafiaError::MathOverflow)?
+        .checked_div(100)
+        .ok_or(SolanaMafiaError::MathOverflow)?;
+
+    let refund_amount = total_invested
+        .checked_sub(sell_fee)
+        .ok_or(SolanaMafiaError::MathOverflow)?;
+
+    Ok(SellPricing {
+        business_type: business.business_type.to_index() as u8,
+        days_held,
+        base_fee_percent,
+        slot_discount,
+        final_fee_percent,
+        total_invested,
+        remaining_invested,
+        sell_fee,
+        refund_amount,
+    })
+}
+
+/// ⬇️ Цена downgrade_business: возврат стоимости верхнего уровня по графику ранней продажи,
+/// не больше доли этого уровня, ушедшей в treasury
+pub fn price_downgrade_business(
+    player: &PlayerCompact,
+    config: &GameConfig,
+    slot_index: u8,
+    current_time: i64,
+) -> Result<DowngradePricing> {
+    if slot_index >= MAX_REGULAR_SLOTS {
+        return Err(SolanaMafiaError::InvalidSlotIndex.into());
+    }
+
+    let slot = &player.business_slots[slot_index as usize];
+    let business = slot.business.as_ref()
+        .ok_or(SolanaMafiaError::BusinessNotFound)?;
+
+    if !business.is_active {
+        return Err(SolanaMafiaError::BusinessNotActive.into());
+    }
+    if business.upgrade_level == 0 {
+        return Err(SolanaMafiaError::InvalidUpgradeLevel.into());
+    }
+    // Записан только последний уровень: одно снятие на одно улучшение
+    if !business.can_downgrade() {
+        return Err(SolanaMafiaError::NoDowngradableUpgrade.into());
+    }
+
+    let (days_held, base_fee_percent, slot_discount, final_fee_percent) =
+        early_sell_fee(slot, business, config, current_time);
+
+    let upgrade_cost = business.top_upgrade_cost();
+    let downgrade_fee = upgrade_cost
+        .checked_mul(final_fee_percent as u64)
+        .ok_or(SolanaMafiaError::MathOverflow)?
+        / 100;
+
+    // Возврат не больше доли, ушедшей в treasury: иначе upgrade + downgrade
+    // без комиссии (скидка слота) выкачивал бы из treasury team fee
+    let refund_amount = (upgrade_cost - downgrade_fee).min(business.top_upgrade_treasury_share());
+
+    Ok(DowngradePricing {
+        old_level: business.upgrade_level,
+        upgrade_cost,
+        rate_bonus: business.top_upgrade_rate_bonus(),
+        days_held,
+        base_fee_percent,
+        slot_discount,
+        final_fee_percent,
+        downgrade_fee,
+        refund_amount,
+    })
+}
+
+/// 🛡️ Slippage: итоговый платеж не больше max_total_payment (если задан)
+pub fn check_max_payment(total_payment: u64, max_total_payment: Option<u64>) -> Result<()> {
+    if let Some(max_total_payment) = max_total_payment {
+        require!(total_payment <= max_total_payment, SolanaMafiaError::MaxPaymentExceeded);
+    }
+    Ok(())
+}
+
+/// 🛡️ Slippage: выплата не меньше минимума (если задан)
+pub fn check_min_amount(amount: u64, min_amount: Option<u64>, error: SolanaMafiaError) -> Result<()> {
+    if let Some(min_amount) = min_amount {
+        if amount < min_amount {
+            return Err(error.into());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START: i64 = 1_700_000_000;
+    const PRICE: u64 = 100_000_000;
+    const UPGRADE_COST: u64 = 20_000_000;
+    // Давно купленный бизнес: финальная комиссия 2%
+    const NOW: i64 = START + 60 * EARNINGS_INTERVAL;
+
+    fn player_with(business: Business) -> PlayerCompact {
+        let mut player = PlayerCompact::new(Pubkey::new_unique(), 255, START);
+        player.place_business_in_slot(0, business).unwrap();
+        player
+    }
+
+    fn upgraded_business(treasury_share: u64) -> Business {
+        let mut business = Business::new(BusinessType::TobaccoShop, PRICE, 200, UpgradeModel::Both, START);
+        business.apply_upgrade(1, UPGRADE_COST, treasury_share, 10).unwrap();
+        business
+    }
+
+    #[test]
+    fn downgrade_refund_is_capped_at_treasury_share() {
+        let config = GameConfig::new(Pubkey::new_unique(), 255);
+        let (_, treasury_share) = config.split_team_fee(UPGRADE_COST).unwrap();
+        let player = player_with(upgraded_business(treasury_share));
+
+        let pricing = price_downgrade_business(&player, &config, 0, NOW).unwrap();
+        assert_eq!(pricing.final_fee_percent, FINAL_SELL_FEE_PERCENT);
+        assert_eq!(pricing.downgrade_fee, UPGRADE_COST * 2 / 100);
+        // 98% стоимости больше 80% доли treasury - возврат ограничен долей
+        assert_eq!(pricing.refund_amount, treasury_share);
+        assert_eq!(pricing.rate_bonus, 10);
+    }
+
+    #[test]
+    fn downgrade_refund_of_fee_free_upgrade_pays_cost_minus_sell_fee() {
+        let config = GameConfig::new(Pubkey::new_unique(), 255);
+        // Апгрейд из earnings: team fee не взимался, вся стоимость в treasury
+        let player = player_with(upgraded_business(UPGRADE_COST));
+
+        let pricing = price_downgrade_business(&player, &config, 0, NOW).unwrap();
+        assert_eq!(pricing.refund_amount, UPGRADE_COST - UPGRADE_COST * 2 / 100);
+    }
+
+    #[test]
+    fn downgrade_requires_a_recorded_upgrade() {
+        let config = GameConfig::new(Pubkey::new_unique(), 255);
+        let mut business = upgraded_business(UPGRADE_COST);
+        business.remove_top_upgrade().unwrap();
+        assert!(price_downgrade_business(&player_with(business), &config, 0, NOW).is_err());
+
+        let fresh = Business::new(BusinessType::TobaccoShop, PRICE, 200, UpgradeModel::Both, START);
+        assert!(price_downgrade_business(&player_with(fresh), &config, 0, NOW).is_err());
+    }
+
+    #[test]
+    fn partial_sell_keeps_base_at_min_deposit() {
+        let config = GameConfig::new(Pubkey::new_unique(), 255);
+        let mut business = upgraded_business(UPGRADE_COST);
+        business.add_principal(PRICE).unwrap();
+        let player = player_with(business);
+
+        let pricing = price_sell_business(&player, &config, 0, Some(PRICE), NOW).unwrap();
+        assert_eq!(pricing.total_invested, PRICE);
+        assert_eq!(pricing.remaining_invested, PRICE + UPGRADE_COST);
+        assert_eq!(pricing.refund_amount, PRICE - PRICE * 2 / 100);
+
+        // Апгрейд не выводится partial sell, база не опускается ниже минимума
+        assert!(price_sell_business(&player, &config, 0, Some(PRICE + 1), NOW).is_err());
+        assert!(price_sell_business(&player, &config, 0, Some(0), NOW).is_err());
+    }
+
+    #[test]
+    fn full_sell_refunds_everything_minus_fee() {
+        let config = GameConfig::new(Pubkey::new_unique(), 255);
+        let player = player_with(upgraded_business(UPGRADE_COST));
+
+        let pricing = price_sell_business(&player, &config, 0, None, START + EARNINGS_INTERVAL).unwrap();
+        assert_eq!(pricing.final_fee_percent, 25);
+        assert_eq!(pricing.total_invested, PRICE + UPGRADE_COST);
+        assert_eq!(pricing.refund_amount, (PRICE + UPGRADE_COST) * 75 / 100);
+    }
+}
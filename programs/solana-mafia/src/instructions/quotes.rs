@@ -0,0 +1,129 @@
+// 🧾 Read-only quote_* инструкции: тот же расчет, что в create/upgrade/sell (instructions::pricing),
+// результат возвращается через return data (simulateTransaction).
+// Пауза и закрытая регистрация проверяются так же, как в самих инструкциях.
+
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::SolanaMafiaError;
+use crate::state::*;
+use crate::instructions::pricing::{price_create_business, price_sell_business, price_upgrade_business};
+
+/// 🧾 Quote create_business (target_level = None) / create_business_with_level (Some)
+/// Без player аккаунта = новый игрок
+pub fn quote_create_business(
+    ctx: Context<crate::QuoteCreateBusiness>,
+    business_type: u8,
+    deposit_amount: u64,
+    slot_index: u8,
+    target_level: Option<u8>,
+) -> Result<crate::QuoteBreakdown> {
+    let game_config = &ctx.accounts.game_config;
+    let clock = Clock::get()?;
+    ctx.accounts.game_state.require_not_paused(PAUSE_NEW_BUSINESSES)?;
+
+    // Новый игрок: пустые слоты + entry fee, как при авто-инициализации в create_business
+    let new_player;
+    let (player, entry_fee) = match ctx.accounts.player.as_deref() {
+        Some(player) if player.created_at != 0 => (&**player, 0),
+        _ => {
+            require!(game_config.registrations_open, SolanaMafiaError::RegistrationsClosed);
+            new_player = PlayerCompact::new(Pubkey::default(), 0, clock.unix_timestamp);
+            (&new_player, game_config.get_current_entry_fee(ctx.accounts.game_state.total_players))
+        }
+    };
+
+    let pricing = price_create_business(player, game_config, business_type, deposit_amount, slot_index, target_level)?;
+
+    let level = target_level.unwrap_or(0);
+    let daily_rate = if level > 0 {
+        Business::create_upgraded(
+            pricing.business_type,
+            pricing.base_cost,
+            pricing.daily_rate,
+            level,
+            pricing.upgrade_costs,
+            game_config,
+            clock.unix_timestamp,
+        )?.daily_rate
+    } else {
+        pricing.daily_rate
+    };
+
+    Ok(crate::QuoteBreakdown {
+        total_payment: pricing.total_payment.saturating_add(entry_fee),
+        entry_fee,
+        deposit_amount,
+        upgrade_cost: pricing.upgrade_costs.iter().sum(),
+        slot_cost: pricing.slot_cost,
+        team_fee: pricing.team_fee
+            .saturating_add(pricing.slot_cost)
+            .saturating_add(entry_fee),
+        treasury_amount: pricing.treasury_amount,
+        fee_percent: game_config.treasury_fee_percent,
+        slot_discount: 0,
+        gross_return: 0,
+        net_return: 0,
+        owed_amount: 0,
+        level,
+        daily_rate,
+    })
+}
+
+/// 🧾 Quote upgrade_business
+pub fn quote_upgrade_business(ctx: Context<crate::QuoteUpgradeBusiness>, slot_index: u8) -> Result<crate::QuoteBreakdown> {
+    let game_config = &ctx.accounts.game_config;
+    ctx.accounts.game_state.require_not_paused(PAUSE_UPGRADES)?;
+    let pricing = price_upgrade_business(&ctx.accounts.player, game_config, slot_index)?;
+
+    Ok(crate::QuoteBreakdown {
+        total_payment: pricing.upgrade_cost,
+        entry_fee: 0,
+        deposit_amount: 0,
+        upgrade_cost: pricing.upgrade_cost,
+        slot_cost: 0,
+        team_fee: pricing.team_fee,
+        treasury_amount: pricing.treasury_amount,
+        fee_percent: game_config.treasury_fee_percent,
+        slot_discount: 0,
+        gross_return: 0,
+        net_return: 0,
+        owed_amount: 0,
+        level: pricing.next_level,
+        daily_rate: pricing.new_daily_rate,
+    })
+}
+
+/// 🧾 Quote sell_business (с учетом pro-rata режима treasury)
+pub fn quote_sell_business(
+    ctx: Context<crate::QuoteSellBusiness>,
+    slot_index: u8,
+    amount: Option<u64>,
+) -> Result<crate::QuoteBreakdown> {
+    let game_config = &ctx.accounts.game_config;
+    let clock = Clock::get()?;
+    ctx.accounts.game_state.require_not_paused(PAUSE_SELLS)?;
+    let pricing = price_sell_business(&ctx.accounts.player, game_config, slot_index, amount, clock.unix_timestamp)?;
+
+    // Симулируем выплату на копии GameState
+    let available = Treasury::available_balance(&ctx.accounts.treasury_pda.to_account_info())?;
+    let mut game_state = GameState::clone(&ctx.accounts.game_state);
+    let (paid, owed) = game_state.plan_payout(pricing.refund_amount, available, game_config.pro_rata_max_draw_bps)?;
+
+    Ok(crate::QuoteBreakdown {
+        total_payment: 0,
+        entry_fee: 0,
+        deposit_amount: pricing.total_invested,
+        upgrade_cost: 0,
+        slot_cost: 0,
+        team_fee: 0,
+        treasury_amount: 0,
+        fee_percent: pricing.final_fee_percent,
+        slot_discount: pricing.slot_discount,
+        gross_return: pricing.refund_amount,
+        net_return: paid,
+        owed_amount: owed,
+        level: 0,
+        daily_rate: 0,
+    })
+}
@@ -0,0 +1,97 @@
+// 🤝 Реферальная программа: бонусы цепочке рефереров из командной комиссии
+// Player PDA рефереров передаются в remaining accounts после получателей FeeSplit:
+// [уровень 1 (player.referrer), уровень 2 (его referrer), ...] до game_config.referral_depth
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::constants::*;
+use crate::state::*;
+use crate::error::SolanaMafiaError;
+
+/// Прочитать Player PDA реферера и проверить, что это PDA именно этого кошелька
+fn load_referrer_player(account: &AccountInfo, referrer: &Pubkey) -> Result<PlayerCompact> {
+    let (expected, _) = Pubkey::find_program_address(&[PLAYER_SEED, referrer.as_ref()], &crate::ID);
+    require_keys_eq!(account.key(), expected, SolanaMafiaError::InvalidReferrer);
+    require_keys_eq!(*account.owner, crate::ID, SolanaMafiaError::InvalidReferrer);
+
+    let data = account.try_borrow_data()?;
+    let player = PlayerCompact::try_deserialize(&mut &data[..])?;
+    require!(player.created_at != 0, SolanaMafiaError::InvalidReferrer);
+    Ok(player)
+}
+
+/// Провалидировать реферера нового игрока (должен быть зарегистрированным игроком)
+pub fn validate_new_referrer(
+    owner: &Pubkey,
+    referrer: Option<Pubkey>,
+    referrer_accounts: &[AccountInfo],
+) -> Result<Pubkey> {
+    let Some(referrer) = referrer else {
+        return Ok(Pubkey::default());
+    };
+    require_keys_neq!(referrer, *owner, SolanaMafiaError::CannotReferYourself);
+
+    let account = referrer_accounts.first().ok_or(SolanaMafiaError::InvalidReferrer)?;
+    load_referrer_player(account, &referrer)?;
+    Ok(referrer)
+}
+
+/// Выплатить бонусы цепочке рефереров из team_fee (CPI с кошелька игрока на Player PDA рефереров).
+/// Возвращает остаток командной комиссии.
+pub fn pay_referral_bonuses<'info>(
+    system_program: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    referee: Pubkey,
+    first_referrer: Pubkey,
+    referrer_accounts: &[AccountInfo<'info>],
+    game_config: &GameConfig,
+    team_fee: u64,
+) -> Result<u64> {
+    let depth = game_config.referral_depth as usize;
+    require!(referrer_accounts.len() <= depth, SolanaMafiaError::ReferrerChainTooDeep);
+
+    let clock = Clock::get()?;
+    let mut remaining_fee = team_fee;
+    let mut referrer = first_referrer;
+
+    for (level, &bonus_bps) in game_config.referral_bonus_bps.iter().take(depth).enumerate() {
+        if referrer == Pubkey::default() {
+            break;
+        }
+        let account = referrer_accounts.get(level).ok_or(SolanaMafiaError::InvalidReferrer)?;
+        require!(account.is_writable, SolanaMafiaError::InvalidReferrer);
+        let mut referrer_player = load_referrer_player(account, &referrer)?;
+
+        let bonus = (team_fee as u128 * bonus_bps as u128 / 10_000) as u64;
+        if bonus > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    system_program::Transfer {
+                        from: payer.clone(),
+                        to: account.clone(),
+                    },
+                ),
+                bonus,
+            )?;
+            referrer_player.add_referral_bonus(bonus)?;
+            referrer_player.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+            remaining_fee = remaining_fee
+                .checked_sub(bonus)
+                .ok_or(SolanaMafiaError::MathOverflow)?;
+
+            emit!(crate::ReferralBonusPaid {
+                referrer,
+                referee,
+                level: level as u8 + 1,
+                amount: bonus,
+                paid_at: clock.unix_timestamp,
+            });
+        }
+
+        referrer = referrer_player.referrer;
+    }
+
+    Ok(remaining_fee)
+}
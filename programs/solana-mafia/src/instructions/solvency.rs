@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+/// 🏦 Рассчитать выплату игроку из treasury PDA с учетом платежеспособности.
+/// Невыплаченный остаток за вычетом `owed_fee_percent` записывается в долг игрока
+/// и ставит его в FIFO очередь долгов (см. GameState::plan_payout).
+/// Возвращает сумму, которую можно перевести сейчас.
+pub fn plan_treasury_payout(
+    game_state: &mut GameState,
+    game_config: &GameConfig,
+    player: &mut PlayerCompact,
+    treasury_info: &AccountInfo,
+    amount: u64,
+    owed_fee_percent: u8,
+) -> Result<u64> {
+    let available = Treasury::available_balance(treasury_info)?;
+    let was_pro_rata = game_state.pro_rata_mode;
+    let (paid, shortfall) = game_state.plan_payout(amount, available, game_config.pro_rata_max_draw_bps)?;
+    // Комиссия с отложенной части не переводится команде, а остается в treasury
+    let owed = shortfall - shortfall * owed_fee_percent as u64 / 100;
+    let clock = Clock::get()?;
+
+    if game_state.pro_rata_mode != was_pro_rata {
+        emit!(crate::SolvencyModeChanged {
+            pro_rata_mode: game_state.pro_rata_mode,
+            available_reserves: available,
+            total_owed: game_state.total_owed,
+            changed_at: clock.unix_timestamp,
+        });
+        msg!("🏦 Pro-rata mode: {} (reserves: {}, owed: {})", game_state.pro_rata_mode, available, game_state.total_owed);
+    }
+
+    if owed > 0 {
+        if !player.has_debt() {
+            player.debt_ticket = game_state.issue_debt_ticket()?;
+        }
+        player.add_owed_earnings(owed)?;
+        game_state.add_debt(owed)?;
+        emit!(crate::DebtRecorded {
+            player: player.owner,
+            requested: amount,
+            paid,
+            owed,
+            player_owed: player.owed_earnings,
+            ticket: player.debt_ticket,
+            total_owed: game_state.total_owed,
+            recorded_at: clock.unix_timestamp,
+        });
+        msg!("🏦 Partial payout {} of {}, {} recorded as debt", paid, amount, owed);
+    }
+
+    Ok(paid)
+}
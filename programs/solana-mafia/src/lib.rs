@@ -13,6 +13,7 @@ use constants::*;
 pub struct PlayerCreated {
     pub wallet: Pubkey,
     pub entry_fee: u64,
+    pub referrer: Pubkey,
     pub created_at: i64,
 }
 
@@ -32,7 +33,10 @@ pub struct BusinessCreated {
 #[event]
 pub struct AutoClaimPurchased {
     pub player: Pubkey,
+    pub tier: u8,
+    pub days: u16,
     pub cost: u64,
+    pub expires_at: i64,
     pub purchased_at: i64,
 }
 
@@ -101,6 +105,271 @@ pub struct BusinessSoldFromSlot {
     pub sold_at: i64,
 }
 
+#[event]
+pub struct SlotEarningsClaimed {
+    pub player: Pubkey,
+    pub slot_index: u8,
+    pub amount: u64,
+    pub net_amount: u64,
+    pub claim_fee: u64,
+    pub claimed_at: i64,
+}
+
+#[event]
+pub struct EarningsReinvested {
+    pub player: Pubkey,
+    pub slot_index: u8,
+    pub claimed_amount: u64,
+    pub reinvest_fee: u64,
+    pub reinvested_amount: u64,
+    pub upgrade_cost: u64,
+    pub new_level: u8,
+    pub new_total_invested: u64,
+    pub new_daily_rate: u16,
+    pub reinvested_at: i64,
+}
+
+#[event]
+pub struct BusinessPartiallySold {
+    pub player: Pubkey,
+    pub slot_index: u8,
+    pub business_type: u8,
+    pub amount: u64,
+    pub remaining_invested: u64,
+    pub days_held: u64,
+    pub final_fee_percent: u8,
+    pub slot_discount: u8,
+    pub return_amount: u64,
+    pub sold_at: i64,
+}
+
+#[event]
+pub struct BusinessDowngraded {
+    pub player: Pubkey,
+    pub slot_index: u8,
+    pub old_level: u8,
+    pub new_level: u8,
+    pub upgrade_cost: u64,
+    pub final_fee_percent: u8,
+    pub slot_discount: u8,
+    pub return_amount: u64,
+    pub new_daily_rate: u16,
+    pub downgraded_at: i64,
+}
+
+/// Admin changed a GameConfig parameter. Multi-value parameters are
+/// flattened in the order of the instruction arguments.
+#[event]
+pub struct ConfigUpdated {
+    pub authority: Pubkey,
+    pub parameter: ConfigParameter,
+    pub old_value: Vec<u64>,
+    pub new_value: Vec<u64>,
+    pub updated_at: i64,
+}
+
+/// 🤖 Player authorized a keeper to claim on their behalf
+#[event]
+pub struct ClaimDelegateSet {
+    pub player: Pubkey,
+    pub keeper: Pubkey,
+    pub tip_bps: u16,
+    pub set_at: i64,
+}
+
+/// 🤖 Player revoked their claim delegate
+#[event]
+pub struct ClaimDelegateRevoked {
+    pub player: Pubkey,
+    pub keeper: Pubkey,
+    pub revoked_at: i64,
+}
+
+/// 🤖 Keeper claimed for a player (net_amount to the player, keeper_tip to the keeper)
+#[event]
+pub struct DelegatedClaim {
+    pub player: Pubkey,
+    pub keeper: Pubkey,
+    pub amount: u64,
+    pub net_amount: u64,
+    pub claim_fee: u64,
+    pub keeper_tip: u64,
+    pub claimed_at: i64,
+}
+
+#[event]
+pub struct PauseUpdated {
+    pub authority: Pubkey,
+    pub is_paused: bool,
+    pub pause_flags: u8,
+    pub pause_allowlist: u8,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub proposed_at: i64,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub transferred_at: i64,
+}
+
+#[event]
+pub struct AuthorityRenounced {
+    pub old_authority: Pubkey,
+    pub renounced_at: i64,
+}
+
+#[event]
+pub struct TreasuryWalletChangeScheduled {
+    pub current_wallet: Pubkey,
+    pub pending_wallet: Pubkey,
+    pub available_at: i64,
+    pub scheduled_at: i64,
+}
+
+#[event]
+pub struct TreasuryWalletChangeCancelled {
+    pub current_wallet: Pubkey,
+    pub cancelled_wallet: Pubkey,
+    pub cancelled_at: i64,
+}
+
+#[event]
+pub struct TreasuryWalletChangeApplied {
+    pub old_wallet: Pubkey,
+    pub new_wallet: Pubkey,
+    pub applied_at: i64,
+}
+
+#[event]
+pub struct FeeRecipientsUpdated {
+    pub authority: Pubkey,
+    pub old_recipients: Vec<FeeRecipient>,
+    pub new_recipients: Vec<FeeRecipient>,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct ConfigChangeQueued {
+    pub id: u64,
+    pub change: ConfigChange,
+    pub queued_by: Pubkey,
+    pub executable_at: i64,
+    pub queued_at: i64,
+}
+
+#[event]
+pub struct ConfigChangeCancelled {
+    pub id: u64,
+    pub change: ConfigChange,
+    pub cancelled_by: Pubkey,
+    pub cancelled_at: i64,
+}
+
+#[event]
+pub struct ConfigChangeExecuted {
+    pub id: u64,
+    pub change: ConfigChange,
+    pub executed_at: i64,
+}
+
+#[event]
+pub struct AdminCouncilUpdated {
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+    pub version: u64,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub id: u64,
+    pub action: AdminAction,
+    pub proposer: Pubkey,
+    pub created_at: i64,
+}
+
+#[event]
+pub struct ProposalApproved {
+    pub id: u64,
+    pub approver: Pubkey,
+    pub approval_count: u8,
+    pub threshold: u8,
+    pub approved_at: i64,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub id: u64,
+    pub action: AdminAction,
+    pub executed_by: Pubkey,
+    pub executed_at: i64,
+}
+
+#[event]
+pub struct ProposalClosed {
+    pub id: u64,
+    pub executed: bool,
+    pub closed_at: i64,
+}
+
+/// Treasury switched between normal and pro-rata payout mode
+#[event]
+pub struct SolvencyModeChanged {
+    pub pro_rata_mode: bool,
+    pub available_reserves: u64,
+    pub total_owed: u64,
+    pub changed_at: i64,
+}
+
+/// Payout was partially deferred - the remainder is owed to the player
+#[event]
+pub struct DebtRecorded {
+    pub player: Pubkey,
+    pub requested: u64,
+    pub paid: u64,
+    pub owed: u64,
+    pub player_owed: u64,
+    pub ticket: u64,
+    pub total_owed: u64,
+    pub recorded_at: i64,
+}
+
+/// Owed earnings were (partially) repaid from the debt queue
+#[event]
+pub struct OwedRedeemed {
+    pub player: Pubkey,
+    pub ticket: u64,
+    pub amount: u64,
+    pub remaining: u64,
+    pub total_owed: u64,
+    pub redeemed_at: i64,
+}
+
+#[event]
+pub struct ReferralBonusPaid {
+    pub referrer: Pubkey,
+    pub referee: Pubkey,
+    pub level: u8,
+    pub amount: u64,
+    pub paid_at: i64,
+}
+
+#[event]
+pub struct ReferralBonusClaimed {
+    pub referrer: Pubkey,
+    pub amount: u64,
+    pub total_referral_earned: u64,
+    pub claimed_at: i64,
+}
+
 // ============ FRONTEND DATA STRUCTURES ============
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -109,9 +378,12 @@ pub struct PlayerFrontendData {
     pub total_invested: u64,
     pub total_earned: u64,
     pub claimable_earnings: u64,       // 🆕 Заменяет pending_earnings
+    pub owed_earnings: u64,            // 🧾 Долг treasury перед игроком
+    pub pending_referral_earnings: u64, // 🤝 Несобранные реферальные бонусы
     pub businesses_count: u8,
     pub active_businesses: u8,
-    pub auto_claim_purchased: bool,    // 🆕 Показать куплены ли автонакопления
+    pub auto_claim_purchased: bool,    // 🆕 Активна ли подписка auto-claim
+    pub auto_claim_expires_at: i64,    // 🔁 Конец подписки (0 = не покупалась)
     pub can_claim: bool,               // 🆕 Можно ли клэймить сейчас
 }
 
@@ -123,103 +395,56 @@ pub struct GlobalStats {
     pub total_withdrawn: u64,
     pub total_businesses: u64,
     pub total_treasury_collected: u64,
+    pub total_owed: u64,               // 🏦 Невыплаченные долги игрокам
+    pub pro_rata_mode: bool,           // 🏦 Treasury в pro-rata режиме
 }
 
-declare_id!("9h2uDYXv48GAfSXzprXDgDKBCkxAv7yRY2pDbZeGnZXF");
+/// Данные одного слота игрока (return data get_valid_player_businesses)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerSlotData {
+    pub slot_index: u8,
+    pub slot_type: u8,                 // SlotType как u8 (0 = Basic ... 3 = Legendary)
+    pub is_unlocked: bool,
+    pub is_paid: bool,
+    pub has_business: bool,
+    pub business_type: u8,             // Поля ниже = 0, если has_business = false
+    pub upgrade_level: u8,
+    pub base_invested_amount: u64,
+    pub total_invested_amount: u64,
+    pub daily_rate: u16,
+    pub is_active: bool,
+    pub purchased_at: u32,
+    pub last_claim_at: u32,            // 0 = еще не было claim
+}
 
-#[program]
-pub mod solana_mafia {
-    use super::*;
-
-    /// Initialize the game with treasury wallet
-    pub fn initialize(ctx: Context<Initialize>, treasury_wallet: Pubkey) -> Result<()> {
-        instructions::admin::initialize(ctx, treasury_wallet)
-    }
-
-    /// Create new player (with entry fee)
-    pub fn create_player(ctx: Context<CreatePlayer>) -> Result<()> {
-        instructions::player::create_player(ctx)
-    }
-
-    /// 🏪 Create business in specific slot
-    pub fn create_business(
-        ctx: Context<CreateBusinessInSlot>,
-        business_type: u8,
-        deposit_amount: u64,
-        slot_index: u8,
-    ) -> Result<()> {
-        instructions::business::create_business(ctx, business_type, deposit_amount, slot_index)
-    }
-
-    /// 🆕 Create business with target level (immediate upgrades)
-    pub fn create_business_with_level(
-        ctx: Context<CreateBusinessInSlot>,
-        business_type: u8,
-        deposit_amount: u64,
-        slot_index: u8,
-        target_level: u8,
-    ) -> Result<()> {
-        instructions::business::create_business_with_level(ctx, business_type, deposit_amount, slot_index, target_level)
-    }
-
-    /// Claim earnings with new individual business tracking system
-    pub fn claim_earnings(ctx: Context<ClaimEarnings>) -> Result<()> {
-        instructions::earnings::claim_earnings(ctx)
-    }
-
-    /// 🆕 Purchase auto claim feature for 0.05 SOL (one-time payment)
-    pub fn purchase_auto_claim(ctx: Context<PurchaseAutoClaim>) -> Result<()> {
-        instructions::earnings::purchase_auto_claim(ctx)
-    }
-
-    /// Health check for player data
-    pub fn health_check_player(ctx: Context<HealthCheckPlayer>) -> Result<()> {
-        instructions::player::health_check_player(ctx)
-    }
-
-    /// 🔥 Sell business from slot (with early exit fees)
-    pub fn sell_business(
-        ctx: Context<SellBusinessFromSlot>,
-        slot_index: u8,
-    ) -> Result<()> {
-        instructions::business::sell_business(ctx, slot_index)
-    }
-
-
-
-
-    /// 🆕 Обновить индекс данных игрока (для бэкенда) - фронтенд должен читать напрямую!
-    pub fn update_player_index(ctx: Context<GetPlayerData>) -> Result<()> {
-        instructions::player::get_player_data(ctx)
-    }
-
-    /// 🆕 Получить глобальную статистику
-    pub fn get_global_stats(ctx: Context<GetGlobalStats>) -> Result<()> {
-        instructions::earnings::get_global_stats(ctx)
-    }
-
-    /// 🆕 Получить только валидные (принадлежащие) бизнесы игрока
-    pub fn get_valid_player_businesses(ctx: Context<GetValidPlayerBusinesses>) -> Result<()> {
-        instructions::player::get_valid_player_businesses(ctx)
-    }
-
-    // 🏪 Старые функции unlock_business_slot и buy_premium_slot удалены
-    // В новой системе все слоты автоматически доступны и оплачиваются при первом использовании
-
-    /// ⬆️ Upgrade business in slot
-    pub fn upgrade_business(
-        ctx: Context<UpgradeBusinessInSlot>,
-        slot_index: u8,
-    ) -> Result<()> {
-        instructions::business::upgrade_business(ctx, slot_index)
-    }
-    
-    /// 💰 Update entry fee (admin only) - for backend control and promotions
-    pub fn update_entry_fee(ctx: Context<UpdateEntryFee>, new_fee_lamports: u64) -> Result<()> {
-        instructions::admin::update_entry_fee(ctx, new_fee_lamports)
-    }
+/// Разбивка цены для quote_* инструкций (неприменимые поля = 0)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuoteBreakdown {
+    pub total_payment: u64,            // Сколько заплатит игрок (entry fee + депозит + слот / upgrade)
+    pub entry_fee: u64,                // Только для нового игрока
+    pub deposit_amount: u64,           // Возвратная часть (для продажи - total_invested бизнеса)
+    pub upgrade_cost: u64,
+    pub slot_cost: u64,
+    pub team_fee: u64,                 // Всего команде (до реферальных бонусов)
+    pub treasury_amount: u64,          // В treasury PDA
+    pub fee_percent: u8,               // treasury_fee_percent / итоговая комиссия продажи
+    pub slot_discount: u8,             // Скидка слота на комиссию продажи
+    pub gross_return: u64,             // Возврат после комиссии продажи
+    pub net_return: u64,               // Выплата сейчас (с учетом pro-rata режима)
+    pub owed_amount: u64,              // Остаток, который будет записан в долг
+    pub level: u8,                     // Уровень бизнеса после операции
+    pub daily_rate: u16,               // Доходность после операции (basis points)
 }
 
+declare_id!("9h2uDYXv48GAfSXzprXDgDKBCkxAv7yRY2pDbZeGnZXF");
+
+// Anchor's generated IDL instructions call the deprecated AccountInfo::realloc
+#[allow(deprecated)]
+mod program_entry;
+pub use program_entry::*;
+
 // ===== ACCOUNT CONTEXTS =====
 
 #[derive(Accounts)]
@@ -253,10 +478,32 @@ pub struct Initialize<'info> {
         bump
     )]
     pub treasury_pda: Account<'info, Treasury>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = FeeSplit::SIZE,
+        seeds = [FEE_SPLIT_SEED],
+        bump
+    )]
+    pub fee_split: Box<Account<'info, FeeSplit>>,
+
+    /// 🔒 Инициализировать игру может только upgrade authority программы
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ error::SolanaMafiaError::UnauthorizedAdmin
+    )]
+    pub program: Program<'info, program::SolanaMafia>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ error::SolanaMafiaError::UnauthorizedAdmin
+    )]
+    pub program_data: Account<'info, ProgramData>,
     
     pub system_program: Program<'info, System>,
 }
 
+/// Remaining accounts: FeeSplit recipient wallets (writable, in FeeSplit order),
+/// then referrer chain Player PDAs (writable, level 1 first)
 #[derive(Accounts)]
 pub struct CreatePlayer<'info> {
     #[account(mut)]
@@ -289,6 +536,12 @@ pub struct CreatePlayer<'info> {
     #[account(mut)]
     pub treasury_wallet: AccountInfo<'info>,
 
+    #[account(
+        seeds = [FEE_SPLIT_SEED],
+        bump = fee_split.bump
+    )]
+    pub fee_split: Box<Account<'info, FeeSplit>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -321,9 +574,16 @@ pub struct PurchaseAutoClaim<'info> {
     )]
     pub game_state: Account<'info, GameState>,
 
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
+
     pub system_program: Program<'info, System>,
 }
 
+/// Remaining accounts: FeeSplit recipient wallets (writable, in FeeSplit order)
 #[derive(Accounts)]
 pub struct ClaimEarnings<'info> {
     #[account(mut)]
@@ -350,8 +610,14 @@ pub struct ClaimEarnings<'info> {
         bump = game_state.bump
     )]
     pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
     
-    /// 💰 Treasury wallet для получения claim fee (0.01 SOL)
+    /// 💰 Treasury wallet для получения claim fee
     /// CHECK: Address is validated against game_state.treasury_wallet constraint
     #[account(
         mut,
@@ -359,72 +625,61 @@ pub struct ClaimEarnings<'info> {
     )]
     pub treasury_wallet: AccountInfo<'info>,
 
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct HealthCheckPlayer<'info> {
     #[account(
-        seeds = [PLAYER_SEED, player.owner.as_ref()],
-        bump = player.bump
+        seeds = [FEE_SPLIT_SEED],
+        bump = fee_split.bump
     )]
-    pub player: Account<'info, Player>,
-}
-
-
+    pub fee_split: Box<Account<'info, FeeSplit>>,
 
+    pub system_program: Program<'info, System>,
+}
 
 #[derive(Accounts)]
-pub struct GetPlayerData<'info> {
+pub struct ReinvestEarnings<'info> {
+    pub player_owner: Signer<'info>,
+    
     #[account(
-        seeds = [PLAYER_SEED, player.owner.as_ref()],
-        bump = player.bump
+        mut,
+        seeds = [PLAYER_SEED, player_owner.key().as_ref()],
+        bump = player.bump,
+        constraint = player.owner == player_owner.key()
     )]
-    pub player: Account<'info, Player>,
-}
+    pub player: Box<Account<'info, Player>>,
 
-#[derive(Accounts)]
-pub struct GetGlobalStats<'info> {
     #[account(
+        mut,
         seeds = [GAME_STATE_SEED],
         bump = game_state.bump
     )]
     pub game_state: Account<'info, GameState>,
-}
-
-
-
-
 
-
-
-
-
-#[derive(Accounts)]
-pub struct GetValidPlayerBusinesses<'info> {
     #[account(
-        seeds = [PLAYER_SEED, player.owner.as_ref()],
-        bump = player.bump
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump
     )]
-    pub player: Account<'info, Player>,
+    pub game_config: Account<'info, GameConfig>,
 }
 
-// 🏪 Старые context структуры UnlockBusinessSlot и BuyPremiumSlot удалены
-// В новой системе все слоты автоматически доступны и оплачиваются в create_business
-
 #[derive(Accounts)]
-pub struct CreateBusinessInSlot<'info> {
+pub struct SetClaimDelegate<'info> {
     #[account(mut)]
-    pub owner: Signer<'info>,
+    pub player_owner: Signer<'info>,
+
+    #[account(
+        seeds = [PLAYER_SEED, player_owner.key().as_ref()],
+        bump = player.bump,
+        constraint = player.owner == player_owner.key()
+    )]
+    pub player: Box<Account<'info, Player>>,
 
     #[account(
         init_if_needed,
-        payer = owner,
-        space = Player::SIZE,
-        seeds = [PLAYER_SEED, owner.key().as_ref()],
+        payer = player_owner,
+        space = ClaimDelegate::SIZE,
+        seeds = [CLAIM_DELEGATE_SEED, player_owner.key().as_ref()],
         bump
     )]
-    pub player: Box<Account<'info, Player>>,
+    pub claim_delegate: Account<'info, ClaimDelegate>,
 
     #[account(
         seeds = [GAME_CONFIG_SEED],
@@ -432,19 +687,49 @@ pub struct CreateBusinessInSlot<'info> {
     )]
     pub game_config: Account<'info, GameConfig>,
 
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeClaimDelegate<'info> {
+    #[account(mut)]
+    pub player_owner: Signer<'info>,
+
     #[account(
         mut,
-        seeds = [GAME_STATE_SEED],
-        bump = game_state.bump
+        close = player_owner,
+        seeds = [CLAIM_DELEGATE_SEED, player_owner.key().as_ref()],
+        bump = claim_delegate.bump
     )]
-    pub game_state: Account<'info, GameState>,
+    pub claim_delegate: Account<'info, ClaimDelegate>,
+}
 
-    /// CHECK: Treasury wallet validated against game_state.treasury_wallet address constraint
+#[derive(Accounts)]
+pub struct CrankClaim<'info> {
+    /// 🤖 Keeper, authorized by claim_delegate (receives the tip)
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    /// CHECK: Receives the earnings, validated against player.owner
     #[account(
         mut,
-        address = game_state.treasury_wallet
+        address = player.owner
     )]
-    pub treasury_wallet: AccountInfo<'info>,
+    pub player_owner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [PLAYER_SEED, player.owner.as_ref()],
+        bump = player.bump
+    )]
+    pub player: Box<Account<'info, Player>>,
+
+    #[account(
+        seeds = [CLAIM_DELEGATE_SEED, player.owner.as_ref()],
+        bump = claim_delegate.bump,
+        constraint = claim_delegate.keeper == keeper.key() @ error::SolanaMafiaError::UnauthorizedKeeper
+    )]
+    pub claim_delegate: Account<'info, ClaimDelegate>,
 
     #[account(
         mut,
@@ -453,9 +738,235 @@ pub struct CreateBusinessInSlot<'info> {
     )]
     pub treasury_pda: Account<'info, Treasury>,
 
+    #[account(
+        mut,
+        seeds = [GAME_STATE_SEED],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
+
+    /// 💰 Treasury wallet для получения claim fee
+    /// CHECK: Address is validated against game_state.treasury_wallet constraint
+    #[account(
+        mut,
+        address = game_state.treasury_wallet
+    )]
+    pub treasury_wallet: AccountInfo<'info>,
+
+    #[account(
+        seeds = [FEE_SPLIT_SEED],
+        bump = fee_split.bump
+    )]
+    pub fee_split: Box<Account<'info, FeeSplit>>,
+}
+
+#[derive(Accounts)]
+pub struct CrankClaimBatch<'info> {
+    /// 🤖 Keeper, must be authorized by every claim_delegate in the batch
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury_pda.bump
+    )]
+    pub treasury_pda: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        seeds = [GAME_STATE_SEED],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
+
+    /// 💰 Treasury wallet для получения claim fee
+    /// CHECK: Address is validated against game_state.treasury_wallet constraint
+    #[account(
+        mut,
+        address = game_state.treasury_wallet
+    )]
+    pub treasury_wallet: AccountInfo<'info>,
+
+    #[account(
+        seeds = [FEE_SPLIT_SEED],
+        bump = fee_split.bump
+    )]
+    pub fee_split: Box<Account<'info, FeeSplit>>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemOwed<'info> {
+    /// CHECK: Receives the repayment, validated against player.owner
+    #[account(
+        mut,
+        address = player.owner
+    )]
+    pub player_owner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [PLAYER_SEED, player.owner.as_ref()],
+        bump = player.bump
+    )]
+    pub player: Box<Account<'info, Player>>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury_pda.bump
+    )]
+    pub treasury_pda: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        seeds = [GAME_STATE_SEED],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimReferralBonus<'info> {
+    #[account(mut)]
+    pub player_owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PLAYER_SEED, player_owner.key().as_ref()],
+        bump = player.bump,
+        constraint = player.owner == player_owner.key()
+    )]
+    pub player: Box<Account<'info, Player>>,
+
+    #[account(
+        seeds = [GAME_STATE_SEED],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+}
+
+#[derive(Accounts)]
+pub struct HealthCheckPlayer<'info> {
+    #[account(
+        seeds = [PLAYER_SEED, player.owner.as_ref()],
+        bump = player.bump
+    )]
+    pub player: Account<'info, Player>,
+}
+
+
+
+
+#[derive(Accounts)]
+pub struct GetPlayerData<'info> {
+    #[account(
+        seeds = [PLAYER_SEED, player.owner.as_ref()],
+        bump = player.bump
+    )]
+    pub player: Account<'info, Player>,
+
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
+}
+
+#[derive(Accounts)]
+pub struct GetGlobalStats<'info> {
+    #[account(
+        seeds = [GAME_STATE_SEED],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+}
+
+
+
+
+
+
+
+
+
+#[derive(Accounts)]
+pub struct GetValidPlayerBusinesses<'info> {
+    #[account(
+        seeds = [PLAYER_SEED, player.owner.as_ref()],
+        bump = player.bump
+    )]
+    pub player: Account<'info, Player>,
+}
+
+// 🏪 Старые context структуры UnlockBusinessSlot и BuyPremiumSlot удалены
+// В новой системе все слоты автоматически доступны и оплачиваются в create_business
+
+/// Remaining accounts: FeeSplit recipient wallets (writable, in FeeSplit order),
+/// then referrer chain Player PDAs (writable, level 1 first)
+#[derive(Accounts)]
+pub struct CreateBusinessInSlot<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = Player::SIZE,
+        seeds = [PLAYER_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub player: Box<Account<'info, Player>>,
+
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
+
+    #[account(
+        mut,
+        seeds = [GAME_STATE_SEED],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// CHECK: Treasury wallet validated against game_state.treasury_wallet address constraint
+    #[account(
+        mut,
+        address = game_state.treasury_wallet
+    )]
+    pub treasury_wallet: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury_pda.bump
+    )]
+    pub treasury_pda: Account<'info, Treasury>,
+
+    #[account(
+        seeds = [FEE_SPLIT_SEED],
+        bump = fee_split.bump
+    )]
+    pub fee_split: Box<Account<'info, FeeSplit>>,
+
     pub system_program: Program<'info, System>,
 }
 
+/// Remaining accounts: FeeSplit recipient wallets (writable, in FeeSplit order)
 #[derive(Accounts)]
 pub struct UpgradeBusinessInSlot<'info> {
     #[account(mut)]
@@ -489,6 +1000,19 @@ pub struct UpgradeBusinessInSlot<'info> {
     )]
     pub treasury_wallet: AccountInfo<'info>,
 
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury_pda.bump
+    )]
+    pub treasury_pda: Account<'info, Treasury>,
+
+    #[account(
+        seeds = [FEE_SPLIT_SEED],
+        bump = fee_split.bump
+    )]
+    pub fee_split: Box<Account<'info, FeeSplit>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -535,6 +1059,40 @@ pub struct SellBusinessFromSlot<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct DowngradeBusinessInSlot<'info> {
+    #[account(mut)]
+    pub player_owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [PLAYER_SEED, player_owner.key().as_ref()],
+        bump = player.bump,
+        constraint = player.owner == player_owner.key()
+    )]
+    pub player: Box<Account<'info, Player>>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury_pda.bump
+    )]
+    pub treasury_pda: Box<Account<'info, Treasury>>,
+    
+    #[account(
+        mut,
+        seeds = [GAME_STATE_SEED],
+        bump = game_state.bump
+    )]
+    pub game_state: Box<Account<'info, GameState>>,
+
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+}
+
 #[derive(Accounts)]
 pub struct GetPlayerSlotData<'info> {
     #[account(
@@ -545,8 +1103,7 @@ pub struct GetPlayerSlotData<'info> {
 }
 
 #[derive(Accounts)]
-pub struct UpdateEntryFee<'info> {
-    #[account(mut)]
+pub struct UpdateGameConfig<'info> {
     pub authority: Signer<'info>,
     
     #[account(
@@ -555,6 +1112,413 @@ pub struct UpdateEntryFee<'info> {
         bump = game_config.bump
     )]
     pub game_config: Account<'info, GameConfig>,
-}
 
+    /// 🏛️ Required when the admin council is enabled
+    #[account(
+        seeds = [ADMIN_COUNCIL_SEED],
+        bump = admin_council.bump
+    )]
+    pub admin_council: Option<Account<'info, AdminCouncil>>,
+
+    /// 🗳️ Approved council proposal for this exact action
+    #[account(mut)]
+    pub proposal: Option<Box<Account<'info, Proposal>>>,
+}
 
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [GAME_STATE_SEED],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
+
+    /// 🏛️ Required when the admin council is enabled
+    #[account(
+        seeds = [ADMIN_COUNCIL_SEED],
+        bump = admin_council.bump
+    )]
+    pub admin_council: Option<Account<'info, AdminCouncil>>,
+
+    /// 🗳️ Approved council proposal for this exact action
+    #[account(mut)]
+    pub proposal: Option<Box<Account<'info, Proposal>>>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    pub new_authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
+
+    #[account(
+        mut,
+        seeds = [GAME_STATE_SEED],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+}
+
+#[derive(Accounts)]
+pub struct RenounceAuthority<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
+
+    #[account(
+        mut,
+        seeds = [GAME_STATE_SEED],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// 🏛️ Required when the admin council is enabled
+    #[account(
+        seeds = [ADMIN_COUNCIL_SEED],
+        bump = admin_council.bump
+    )]
+    pub admin_council: Option<Account<'info, AdminCouncil>>,
+
+    /// 🗳️ Approved council proposal for this exact action
+    #[account(mut)]
+    pub proposal: Option<Box<Account<'info, Proposal>>>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTreasuryWallet<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [GAME_STATE_SEED],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
+
+    /// 🏛️ Required when the admin council is enabled
+    #[account(
+        seeds = [ADMIN_COUNCIL_SEED],
+        bump = admin_council.bump
+    )]
+    pub admin_council: Option<Account<'info, AdminCouncil>>,
+
+    /// 🗳️ Approved council proposal for this exact action
+    #[account(mut)]
+    pub proposal: Option<Box<Account<'info, Proposal>>>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyTreasuryWallet<'info> {
+    #[account(
+        mut,
+        seeds = [GAME_STATE_SEED],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeRecipients<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
+
+    #[account(
+        mut,
+        seeds = [FEE_SPLIT_SEED],
+        bump = fee_split.bump
+    )]
+    pub fee_split: Account<'info, FeeSplit>,
+
+    /// 🏛️ Required when the admin council is enabled
+    #[account(
+        seeds = [ADMIN_COUNCIL_SEED],
+        bump = admin_council.bump
+    )]
+    pub admin_council: Option<Account<'info, AdminCouncil>>,
+
+    /// 🗳️ Approved council proposal for this exact action
+    #[account(mut)]
+    pub proposal: Option<Box<Account<'info, Proposal>>>,
+}
+
+#[derive(Accounts)]
+pub struct QueueConfigChange<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = PendingConfigChange::SIZE,
+        seeds = [PENDING_CONFIG_SEED, game_config.next_config_change_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pending_change: Box<Account<'info, PendingConfigChange>>,
+
+    pub system_program: Program<'info, System>,
+
+    /// 🏛️ Required when the admin council is enabled
+    #[account(
+        seeds = [ADMIN_COUNCIL_SEED],
+        bump = admin_council.bump
+    )]
+    pub admin_council: Option<Account<'info, AdminCouncil>>,
+
+    /// 🗳️ Approved council proposal for this exact action
+    #[account(mut)]
+    pub proposal: Option<Box<Account<'info, Proposal>>>,
+}
+
+#[derive(Accounts)]
+pub struct CancelConfigChange<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
+
+    #[account(
+        mut,
+        close = queued_by,
+        seeds = [PENDING_CONFIG_SEED, pending_change.id.to_le_bytes().as_ref()],
+        bump = pending_change.bump
+    )]
+    pub pending_change: Box<Account<'info, PendingConfigChange>>,
+
+    /// CHECK: Rent goes back to whoever queued the change
+    #[account(
+        mut,
+        address = pending_change.queued_by
+    )]
+    pub queued_by: AccountInfo<'info>,
+
+    /// 🏛️ Required when the admin council is enabled
+    #[account(
+        seeds = [ADMIN_COUNCIL_SEED],
+        bump = admin_council.bump
+    )]
+    pub admin_council: Option<Account<'info, AdminCouncil>>,
+
+    /// 🗳️ Approved council proposal for this exact action
+    #[account(mut)]
+    pub proposal: Option<Box<Account<'info, Proposal>>>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteConfigChange<'info> {
+    #[account(
+        mut,
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
+
+    #[account(
+        mut,
+        close = queued_by,
+        seeds = [PENDING_CONFIG_SEED, pending_change.id.to_le_bytes().as_ref()],
+        bump = pending_change.bump
+    )]
+    pub pending_change: Box<Account<'info, PendingConfigChange>>,
+
+    /// CHECK: Rent goes back to whoever queued the change
+    #[account(
+        mut,
+        address = pending_change.queued_by
+    )]
+    pub queued_by: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetAdminCouncil<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AdminCouncil::SIZE,
+        seeds = [ADMIN_COUNCIL_SEED],
+        bump
+    )]
+    pub admin_council: Account<'info, AdminCouncil>,
+
+    /// 🗳️ Approved council proposal (required once the council is enabled)
+    #[account(mut)]
+    pub proposal: Option<Box<Account<'info, Proposal>>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ADMIN_COUNCIL_SEED],
+        bump = admin_council.bump
+    )]
+    pub admin_council: Account<'info, AdminCouncil>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = Proposal::SIZE,
+        seeds = [PROPOSAL_SEED, admin_council.next_proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    pub approver: Signer<'info>,
+
+    #[account(
+        seeds = [ADMIN_COUNCIL_SEED],
+        bump = admin_council.bump
+    )]
+    pub admin_council: Account<'info, AdminCouncil>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+}
+
+#[derive(Accounts)]
+pub struct CloseProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        close = proposer,
+        has_one = proposer,
+        seeds = [PROPOSAL_SEED, proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+}
+
+#[derive(Accounts)]
+pub struct QuoteCreateBusiness<'info> {
+    /// Не передается для нового игрока (цена включит entry fee)
+    #[account(
+        seeds = [PLAYER_SEED, player.owner.as_ref()],
+        bump = player.bump
+    )]
+    pub player: Option<Box<Account<'info, Player>>>,
+
+    #[account(
+        seeds = [GAME_STATE_SEED],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
+}
+
+#[derive(Accounts)]
+pub struct QuoteUpgradeBusiness<'info> {
+    #[account(
+        seeds = [PLAYER_SEED, player.owner.as_ref()],
+        bump = player.bump
+    )]
+    pub player: Box<Account<'info, Player>>,
+
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
+
+    #[account(
+        seeds = [GAME_STATE_SEED],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+}
+
+#[derive(Accounts)]
+pub struct QuoteSellBusiness<'info> {
+    #[account(
+        seeds = [PLAYER_SEED, player.owner.as_ref()],
+        bump = player.bump
+    )]
+    pub player: Box<Account<'info, Player>>,
+
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury_pda.bump
+    )]
+    pub treasury_pda: Account<'info, Treasury>,
+
+    #[account(
+        seeds = [GAME_STATE_SEED],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
+}
@@ -0,0 +1,311 @@
+// 🎮 Инструкции программы. Вынесены из lib.rs, чтобы allow(deprecated) для
+// IDL-обработчиков, которые генерирует #[program], не действовал на весь крейт.
+
+use crate::*;
+
+#[program]
+pub mod solana_mafia {
+    use super::*;
+
+    /// Initialize the game with treasury wallet
+    pub fn initialize(ctx: Context<Initialize>, treasury_wallet: Pubkey) -> Result<()> {
+        instructions::admin::initialize(ctx, treasury_wallet)
+    }
+
+    /// Create new player (with entry fee)
+    pub fn create_player<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreatePlayer<'info>>,
+        referrer: Option<Pubkey>,
+        max_total_payment: Option<u64>,
+    ) -> Result<()> {
+        instructions::player::create_player(ctx, referrer, max_total_payment)
+    }
+
+    /// 🏪 Create business in specific slot
+    /// `max_total_payment` - slippage guard for entry fee (new player) + deposit + slot cost
+    pub fn create_business<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateBusinessInSlot<'info>>,
+        business_type: u8,
+        deposit_amount: u64,
+        slot_index: u8,
+        referrer: Option<Pubkey>,
+        max_total_payment: Option<u64>,
+    ) -> Result<()> {
+        instructions::business::create_business(ctx, business_type, deposit_amount, slot_index, referrer, max_total_payment)
+    }
+
+    /// 🆕 Create business with target level (immediate upgrades)
+    pub fn create_business_with_level<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateBusinessInSlot<'info>>,
+        business_type: u8,
+        deposit_amount: u64,
+        slot_index: u8,
+        target_level: u8,
+        referrer: Option<Pubkey>,
+        max_total_payment: Option<u64>,
+    ) -> Result<()> {
+        instructions::business::create_business_with_level(
+            ctx, business_type, deposit_amount, slot_index, target_level, referrer, max_total_payment,
+        )
+    }
+
+    /// Claim earnings with new individual business tracking system
+    /// `min_claim` - minimum net amount received now (after claim fee and pro-rata)
+    pub fn claim_earnings<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimEarnings<'info>>,
+        min_claim: Option<u64>,
+    ) -> Result<()> {
+        instructions::earnings::claim_earnings(ctx, min_claim)
+    }
+
+    /// 🎯 Claim earnings of one business only (other slots keep their claim timers)
+    /// `min_claim` - minimum net amount received now (after claim fee and pro-rata)
+    pub fn claim_slot_earnings<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimEarnings<'info>>,
+        slot_index: u8,
+        min_claim: Option<u64>,
+    ) -> Result<()> {
+        instructions::earnings::claim_slot_earnings(ctx, slot_index, min_claim)
+    }
+
+    /// 🔁 Reinvest claimable earnings into a business (or fund its next upgrade)
+    /// `min_reinvested` - minimum amount added to the business (after reinvest fee)
+    pub fn reinvest_earnings(
+        ctx: Context<ReinvestEarnings>,
+        slot_index: u8,
+        fund_upgrade: bool,
+        min_reinvested: Option<u64>,
+    ) -> Result<()> {
+        instructions::earnings::reinvest_earnings(ctx, slot_index, fund_upgrade, min_reinvested)
+    }
+
+    /// 🤝 Claim accumulated referral bonuses
+    pub fn claim_referral_bonus(ctx: Context<ClaimReferralBonus>) -> Result<()> {
+        instructions::player::claim_referral_bonus(ctx)
+    }
+
+    /// 🧾 Repay owed earnings to the player at the head of the debt queue (FIFO, anyone can call)
+    pub fn redeem_owed(ctx: Context<RedeemOwed>) -> Result<()> {
+        instructions::earnings::redeem_owed(ctx)
+    }
+
+    /// 🔁 Purchase or extend an auto claim subscription (tier = index in GameConfig auto claim tiers)
+    pub fn purchase_auto_claim(
+        ctx: Context<PurchaseAutoClaim>,
+        tier: u8,
+        max_total_payment: Option<u64>,
+    ) -> Result<()> {
+        instructions::earnings::purchase_auto_claim(ctx, tier, max_total_payment)
+    }
+
+    /// 🤝 Authorize a keeper to claim on the player's behalf (earnings still go to the player)
+    /// `tip_bps` - keeper's share of the net payout, capped by game_config.max_keeper_tip_bps
+    pub fn set_claim_delegate(
+        ctx: Context<SetClaimDelegate>,
+        keeper: Pubkey,
+        tip_bps: u16,
+    ) -> Result<()> {
+        instructions::crank::set_claim_delegate(ctx, keeper, tip_bps)
+    }
+
+    /// 🚫 Revoke the claim delegate and reclaim its rent
+    pub fn revoke_claim_delegate(ctx: Context<RevokeClaimDelegate>) -> Result<()> {
+        instructions::crank::revoke_claim_delegate(ctx)
+    }
+
+    /// 🤖 Claim earnings for a player as their authorized keeper
+    pub fn crank_claim<'info>(
+        ctx: Context<'_, '_, '_, 'info, CrankClaim<'info>>,
+    ) -> Result<()> {
+        instructions::crank::crank_claim(ctx)
+    }
+
+    /// 🤖 Claim earnings for many players in one transaction
+    /// Remaining accounts: fee recipients, then (player, claim_delegate, owner wallet) triples
+    pub fn crank_claim_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, CrankClaimBatch<'info>>,
+    ) -> Result<()> {
+        instructions::crank::crank_claim_batch(ctx)
+    }
+
+    /// Health check for player data
+    pub fn health_check_player(ctx: Context<HealthCheckPlayer>) -> Result<()> {
+        instructions::player::health_check_player(ctx)
+    }
+
+    /// 🔥 Sell business from slot (with early exit fees)
+    /// `min_return` - minimum amount received now (after sell fee and pro-rata)
+    pub fn sell_business(
+        ctx: Context<SellBusinessFromSlot>,
+        slot_index: u8,
+        min_return: Option<u64>,
+    ) -> Result<()> {
+        instructions::business::sell_business(ctx, slot_index, min_return)
+    }
+
+    /// ✂️ Partial sell: вывести часть базового депозита (сверх минимума), бизнес, слот и улучшения остаются
+    /// `min_return` - minimum amount received now (after sell fee and pro-rata)
+    pub fn sell_business_partial(
+        ctx: Context<SellBusinessFromSlot>,
+        slot_index: u8,
+        amount: u64,
+        min_return: Option<u64>,
+    ) -> Result<()> {
+        instructions::business::sell_business_partial(ctx, slot_index, amount, min_return)
+    }
+
+    /// ⬇️ Снять последнее улучшение с частичным возвратом (одно снятие на одно улучшение)
+    pub fn downgrade_business(
+        ctx: Context<DowngradeBusinessInSlot>,
+        slot_index: u8,
+        min_return: Option<u64>,
+    ) -> Result<()> {
+        instructions::business::downgrade_business(ctx, slot_index, min_return)
+    }
+
+
+
+
+    /// 🆕 Данные игрока для фронтенда/бэкенда (return data, читать через simulateTransaction)
+    pub fn update_player_index(ctx: Context<GetPlayerData>) -> Result<PlayerFrontendData> {
+        instructions::player::get_player_data(ctx)
+    }
+
+    /// 🆕 Получить глобальную статистику (return data)
+    pub fn get_global_stats(ctx: Context<GetGlobalStats>) -> Result<GlobalStats> {
+        instructions::earnings::get_global_stats(ctx)
+    }
+
+    /// 🧾 Quote create_business (target_level = None) / create_business_with_level (return data)
+    pub fn quote_create_business(
+        ctx: Context<QuoteCreateBusiness>,
+        business_type: u8,
+        deposit_amount: u64,
+        slot_index: u8,
+        target_level: Option<u8>,
+    ) -> Result<QuoteBreakdown> {
+        instructions::quotes::quote_create_business(ctx, business_type, deposit_amount, slot_index, target_level)
+    }
+
+    /// 🧾 Quote upgrade_business (return data)
+    pub fn quote_upgrade_business(ctx: Context<QuoteUpgradeBusiness>, slot_index: u8) -> Result<QuoteBreakdown> {
+        instructions::quotes::quote_upgrade_business(ctx, slot_index)
+    }
+
+    /// 🧾 Quote sell_business (amount = None) / sell_business_partial (return data)
+    pub fn quote_sell_business(
+        ctx: Context<QuoteSellBusiness>,
+        slot_index: u8,
+        amount: Option<u64>,
+    ) -> Result<QuoteBreakdown> {
+        instructions::quotes::quote_sell_business(ctx, slot_index, amount)
+    }
+
+    /// 🆕 Получить все слоты игрока с бизнесами (return data)
+    pub fn get_valid_player_businesses(ctx: Context<GetValidPlayerBusinesses>) -> Result<Vec<PlayerSlotData>> {
+        instructions::player::get_valid_player_businesses(ctx)
+    }
+
+    // 🏪 Старые функции unlock_business_slot и buy_premium_slot удалены
+    // В новой системе все слоты автоматически доступны и оплачиваются при первом использовании
+
+    /// ⬆️ Upgrade business in slot
+    pub fn upgrade_business<'info>(
+        ctx: Context<'_, '_, '_, 'info, UpgradeBusinessInSlot<'info>>,
+        slot_index: u8,
+        max_total_payment: Option<u64>,
+    ) -> Result<()> {
+        instructions::business::upgrade_business(ctx, slot_index, max_total_payment)
+    }
+    
+    /// 🚪 Close new player registrations instantly (admin only); reopening goes through the timelock
+    pub fn close_registrations(ctx: Context<UpdateGameConfig>) -> Result<()> {
+        instructions::admin::close_registrations(ctx)
+    }
+
+    /// 🚨 Emergency pause: global switch + granular PAUSE_* bits + allowlist (admin only)
+    pub fn set_pause(
+        ctx: Context<SetPause>,
+        is_paused: bool,
+        pause_flags: u8,
+        pause_allowlist: u8,
+    ) -> Result<()> {
+        instructions::admin::set_pause(ctx, is_paused, pause_flags, pause_allowlist)
+    }
+
+    /// 👑 Propose new authority (two-step transfer, admin only)
+    pub fn propose_authority(ctx: Context<UpdateGameConfig>, new_authority: Pubkey) -> Result<()> {
+        instructions::admin::propose_authority(ctx, new_authority)
+    }
+
+    /// 👑 Accept pending authority transfer (signed by new authority)
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        instructions::admin::accept_authority(ctx)
+    }
+
+    /// 🔥 Renounce authority and freeze config forever (admin only)
+    pub fn renounce_authority(ctx: Context<RenounceAuthority>) -> Result<()> {
+        instructions::admin::renounce_authority(ctx)
+    }
+
+    /// 🏦 Schedule new treasury wallet (timelocked, admin only)
+    pub fn schedule_treasury_wallet(ctx: Context<UpdateTreasuryWallet>, new_wallet: Pubkey) -> Result<()> {
+        instructions::admin::schedule_treasury_wallet(ctx, new_wallet)
+    }
+
+    /// 🏦 Cancel scheduled treasury wallet change (admin / council)
+    pub fn cancel_treasury_wallet_change(ctx: Context<UpdateTreasuryWallet>) -> Result<()> {
+        instructions::admin::cancel_treasury_wallet_change(ctx)
+    }
+
+    /// 🏦 Apply scheduled treasury wallet change after timelock (anyone can call)
+    pub fn apply_treasury_wallet_change(ctx: Context<ApplyTreasuryWallet>) -> Result<()> {
+        instructions::admin::apply_treasury_wallet_change(ctx)
+    }
+
+    /// ⏳ Update treasury wallet change delay (admin only)
+    pub fn set_treasury_change_delay(ctx: Context<UpdateTreasuryWallet>, delay: i64) -> Result<()> {
+        instructions::admin::set_treasury_change_delay(ctx, delay)
+    }
+
+    /// 💸 Set team fee recipients with basis-point shares (empty = treasury wallet, admin only)
+    pub fn set_fee_recipients(ctx: Context<SetFeeRecipients>, recipients: Vec<FeeRecipient>) -> Result<()> {
+        instructions::admin::set_fee_recipients(ctx, recipients)
+    }
+
+    /// ⏳ Queue a GameConfig change (rates, deposits, upgrades, fees...) behind config_timelock (admin only)
+    pub fn queue_config_change(ctx: Context<QueueConfigChange>, change: ConfigChange) -> Result<()> {
+        instructions::admin::queue_config_change(ctx, change)
+    }
+
+    /// ⏳ Cancel a queued GameConfig change (admin / council)
+    pub fn cancel_config_change(ctx: Context<CancelConfigChange>) -> Result<()> {
+        instructions::admin::cancel_config_change(ctx)
+    }
+
+    /// ⏳ Execute a queued GameConfig change after its timelock (anyone can call)
+    pub fn execute_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+        instructions::admin::execute_config_change(ctx)
+    }
+
+    /// 🏛️ Set admin council (M-of-N). Once enabled, fee changes, pause, treasury rotation
+    /// and authority transfer require an approved proposal
+    pub fn set_admin_council(ctx: Context<SetAdminCouncil>, members: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        instructions::admin::set_admin_council(ctx, members, threshold)
+    }
+
+    /// 🗳️ Create a council proposal for a sensitive admin action
+    pub fn create_proposal(ctx: Context<CreateProposal>, action: AdminAction) -> Result<()> {
+        instructions::admin::create_proposal(ctx, action)
+    }
+
+    /// 🗳️ Approve a council proposal
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        instructions::admin::approve_proposal(ctx)
+    }
+
+    /// 🗳️ Close a council proposal and return rent to the proposer
+    pub fn close_proposal(ctx: Context<CloseProposal>) -> Result<()> {
+        instructions::admin::close_proposal(ctx)
+    }
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::error::SolanaMafiaError;
+
+/// 🏛️ M-of-N совет админов. Пока game_config.council_enabled == false,
+/// чувствительные операции выполняет один authority.
+#[account]
+pub struct AdminCouncil {
+    pub members: [Pubkey; MAX_COUNCIL_MEMBERS],
+    pub member_count: u8,
+    pub threshold: u8,
+    /// Увеличивается при каждой смене состава - старые одобрения становятся недействительными
+    pub version: u64,
+    pub next_proposal_id: u64,
+    pub bump: u8,
+}
+
+impl AdminCouncil {
+    pub const SIZE: usize = 8 + // discriminator
+        32 * MAX_COUNCIL_MEMBERS + // members
+        1 + // member_count
+        1 + // threshold
+        8 + // version
+        8 + // next_proposal_id
+        1; // bump
+
+    pub fn active_members(&self) -> &[Pubkey] {
+        &self.members[..self.member_count as usize]
+    }
+
+    /// Индекс участника совета (для битовой маски одобрений)
+    pub fn member_index(&self, key: &Pubkey) -> Option<usize> {
+        self.active_members().iter().position(|m| m == key)
+    }
+
+    pub fn require_member(&self, key: &Pubkey) -> Result<usize> {
+        self.member_index(key)
+            .ok_or_else(|| SolanaMafiaError::NotCouncilMember.into())
+    }
+
+    /// Установить состав совета. Пустой список с threshold = 0 отключает совет
+    pub fn set_members(&mut self, members: &[Pubkey], threshold: u8) -> Result<()> {
+        if members.is_empty() {
+            require!(threshold == 0, SolanaMafiaError::InvalidCouncilConfig);
+        } else {
+            require!(
+                members.len() <= MAX_COUNCIL_MEMBERS
+                    && threshold >= 1
+                    && threshold as usize <= members.len(),
+                SolanaMafiaError::InvalidCouncilConfig
+            );
+        }
+
+        for (i, member) in members.iter().enumerate() {
+            require!(
+                *member != Pubkey::default() && !members[..i].contains(member),
+                SolanaMafiaError::InvalidCouncilConfig
+            );
+        }
+
+        self.members = [Pubkey::default(); MAX_COUNCIL_MEMBERS];
+        self.members[..members.len()].copy_from_slice(members);
+        self.member_count = members.len() as u8;
+        self.threshold = threshold;
+        self.version = self.version
+            .checked_add(1)
+            .ok_or(SolanaMafiaError::MathOverflow)?;
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 // state/business.rs - ОБНОВЛЕНО ДЛЯ СИСТЕМЫ УЛУЧШЕНИЙ
 use anchor_lang::prelude::*;
 use crate::constants::*;
+use crate::state::game_config::GameConfig;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
 pub enum BusinessType {
@@ -36,14 +37,14 @@ impl BusinessType {
         }
     }
 
-    /// 🆕 Получить базовую стоимость бизнеса
-    pub fn get_base_cost(&self) -> u64 {
-        MIN_DEPOSITS[self.to_index()]
+    /// 🆕 Получить базовую стоимость бизнеса (из GameConfig)
+    pub fn get_base_cost(&self, config: &GameConfig) -> u64 {
+        config.get_min_deposit(self.to_index())
     }
 
-    /// 🆕 Получить базовую доходность
-    pub fn get_base_rate(&self) -> u16 {
-        BUSINESS_RATES[self.to_index()]
+    /// 🆕 Получить базовую доходность (из GameConfig)
+    pub fn get_base_rate(&self, config: &GameConfig) -> u16 {
+        config.get_business_rate(self.to_index())
     }
 
 
@@ -56,8 +57,8 @@ pub struct Business {
     pub base_invested_amount: u64,       // 🆕 Базовая стоимость (без улучшений)
     pub total_invested_amount: u64,      // 🆕 Общая стоимость (база + все улучшения)
     pub daily_rate: u16,                 // Текущая доходность (база + бонусы улучшений)
-    pub upgrade_level: u8,               // 🆕 Уровень улучшения (0-3)
-    pub upgrade_history: [u64; 3],       // 🆕 История трат на каждое улучшение
+    pub upgrade_level: u8,               // 🆕 Уровень улучшения (0..=GameConfig.max_upgrade_level)
+    pub upgrade_spent: u64,              // 🆕 Сумма трат на все улучшения (не растет с лимитом уровней)
     pub total_earned: u64,
     pub last_claim: i64,                 // DEPRECATED - больше не используется
     pub created_at: i64,
@@ -66,6 +67,12 @@ pub struct Business {
     // 🆕 НОВЫЕ ПОЛЯ ДЛЯ СИСТЕМЫ ИНДИВИДУАЛЬНЫХ CLAIM
     pub purchased_at: u32,               // Время покупки бизнеса (u32 timestamp)
     pub last_claim_at: Option<u32>,      // Время последнего claim для этого бизнеса
+    pub upgrade_model: UpgradeModel,     // 🆕 Модель улучшений (фиксируется при покупке)
+
+    // ⬇️ Последнее примененное улучшение (downgrade снимает только его; 0 = снимать нечего)
+    pub last_upgrade_cost: u64,           // Сколько заплачено за верхний уровень
+    pub last_upgrade_treasury_share: u64, // Сколько из этого ушло в treasury (без team fee)
+    pub last_upgrade_rate_bonus: u16,     // Какой бонус ставки добавлен
 }
 
 impl Business {
@@ -75,21 +82,26 @@ impl Business {
         8 +  // total_invested_amount
         2 +  // daily_rate
         1 +  // upgrade_level
-        24 + // upgrade_history [u64; 3]
+        8 +  // upgrade_spent
         8 +  // total_earned
         8 +  // last_claim (DEPRECATED)
         8 +  // created_at
         1 +  // is_active
         4 +  // purchased_at (u32)
-        5;   // last_claim_at (Option<u32>)
+        5 +  // last_claim_at (Option<u32>)
+        1 +  // upgrade_model
+        8 +  // last_upgrade_cost
+        8 +  // last_upgrade_treasury_share
+        2;   // last_upgrade_rate_bonus
 
     /// Создать новый базовый бизнес
     pub fn new(
         business_type: BusinessType,
         base_amount: u64,
+        base_rate: u16,
+        upgrade_model: UpgradeModel,
         current_time: i64,
     ) -> Self {
-        let base_rate = business_type.get_base_rate();
         let current_time_u32 = Self::timestamp_to_u32(current_time);
         
         Self {
@@ -98,7 +110,7 @@ impl Business {
             total_invested_amount: base_amount, // Изначально равна базовой
             daily_rate: base_rate,
             upgrade_level: 0,
-            upgrade_history: [0; 3],
+            upgrade_spent: 0,
             total_earned: 0,
             last_claim: 0, // DEPRECATED - не используется, но нужно для SIZE
             created_at: current_time,
@@ -107,6 +119,10 @@ impl Business {
             // 🆕 НОВЫЕ ПОЛЯ
             purchased_at: current_time_u32,
             last_claim_at: None, // Пока не было claim
+            upgrade_model,
+            last_upgrade_cost: 0,
+            last_upgrade_treasury_share: 0,
+            last_upgrade_rate_bonus: 0,
         }
     }
 
@@ -114,20 +130,24 @@ impl Business {
     pub fn create_upgraded(
         business_type: BusinessType,
         base_amount: u64,
+        base_rate: u16,
         target_level: u8,
-        upgrade_costs: [u64; 3],
+        upgrade_costs: [u64; MAX_UPGRADE_LEVEL as usize],
+        config: &GameConfig,
         current_time: i64,
     ) -> Result<Self> {
-        if target_level > MAX_UPGRADE_LEVEL {
+        if target_level > config.max_upgrade_level {
             return Err(ProgramError::InvalidArgument.into());
         }
 
-        let mut business = Self::new(business_type, base_amount, current_time);
+        let upgrade_model = config.get_upgrade_model(business_type.to_index());
+        let mut business = Self::new(business_type, base_amount, base_rate, upgrade_model, current_time);
         
         // Применяем все улучшения до целевого уровня
         for level in 1..=target_level {
             let upgrade_cost = upgrade_costs[(level - 1) as usize];
-            business.apply_upgrade(level, upgrade_cost)?;
+            let (_, treasury_share) = config.split_team_fee(upgrade_cost)?;
+            business.apply_upgrade(level, upgrade_cost, treasury_share, config.get_upgrade_bonus(level))?;
         }
         
         // Новые поля уже инициализированы в new()
@@ -135,24 +155,31 @@ impl Business {
     }
 
     /// 🆕 Применить улучшение
-    pub fn apply_upgrade(&mut self, new_level: u8, upgrade_cost: u64) -> Result<()> {
+    pub fn apply_upgrade(&mut self, new_level: u8, upgrade_cost: u64, treasury_share: u64, rate_bonus: u16) -> Result<()> {
         if new_level != self.upgrade_level + 1 || new_level > MAX_UPGRADE_LEVEL {
             return Err(ProgramError::InvalidArgument.into());
         }
 
         // Сохраняем стоимость улучшения
-        self.upgrade_history[(new_level - 1) as usize] = upgrade_cost;
+        self.upgrade_spent = self.upgrade_spent
+            .checked_add(upgrade_cost)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
         
-        // Обновляем общую инвестицию
+        // Обновляем общую инвестицию (всегда - это база для возврата при продаже)
         self.total_invested_amount = self.total_invested_amount
             .checked_add(upgrade_cost)
             .ok_or(ProgramError::ArithmeticOverflow)?;
         
-        // 🔧 ИСПРАВЛЕНИЕ: daily_rate НЕ зависит от уровня апгрейда!
-        // Уровни апгрейда увеличивают только invested_amount, но НЕ процентную ставку
-        // daily_rate остается базовым для всех уровней
-        let base_rate = self.business_type.get_base_rate();
-        self.daily_rate = base_rate;  // Всегда базовая ставка, без бонусов
+        // 📈 Бонус ставки только для RateBonus / Both
+        let applied_bonus = if self.upgrade_model != UpgradeModel::Principal { rate_bonus } else { 0 };
+        self.daily_rate = self.daily_rate
+            .checked_add(applied_bonus)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        // Запоминаем, что именно применено - downgrade снимает ровно это
+        self.last_upgrade_cost = upgrade_cost;
+        self.last_upgrade_treasury_share = treasury_share.min(upgrade_cost);
+        self.last_upgrade_rate_bonus = applied_bonus;
         
         // Обновляем уровень
         self.upgrade_level = new_level;
@@ -160,20 +187,86 @@ impl Business {
         Ok(())
     }
 
-    /// 🆕 Рассчитать стоимость следующего улучшения
-    pub fn calculate_next_upgrade_cost(&self) -> Option<u64> {
-        let next_level = self.upgrade_level + 1;
-        if next_level > MAX_UPGRADE_LEVEL {
-            return None;
+    /// 🆕 Можно ли снять верхний уровень: одно снятие на одно улучшение
+    pub fn can_downgrade(&self) -> bool {
+        self.upgrade_level > 0 && self.last_upgrade_cost > 0
+    }
+
+    /// 🆕 Стоимость верхнего уровня улучшения (для downgrade) - сколько за него реально заплачено.
+    /// Не зависит от текущего GameConfig и base_invested_amount.
+    pub fn top_upgrade_cost(&self) -> u64 {
+        self.last_upgrade_cost.min(self.upgrade_spent)
+    }
+
+    /// 🆕 Доля верхнего уровня, ушедшая в treasury - потолок возврата при downgrade
+    /// (team fee уже выплачен команде и не возвращается)
+    pub fn top_upgrade_treasury_share(&self) -> u64 {
+        self.last_upgrade_treasury_share.min(self.top_upgrade_cost())
+    }
+
+    /// 🆕 Бонус ставки, добавленный верхним уровнем
+    pub fn top_upgrade_rate_bonus(&self) -> u16 {
+        self.last_upgrade_rate_bonus
+    }
+
+    /// 🆕 Снять верхний уровень улучшения (ровно то, что было применено).
+    /// Запись очищается - следующий downgrade возможен только после нового улучшения.
+    pub fn remove_top_upgrade(&mut self) -> Result<()> {
+        if !self.can_downgrade() {
+            return Err(ProgramError::InvalidArgument.into());
+        }
+
+        let upgrade_cost = self.top_upgrade_cost();
+        let rate_bonus = self.top_upgrade_rate_bonus();
+        self.upgrade_spent = self.upgrade_spent
+            .checked_sub(upgrade_cost)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.total_invested_amount = self.total_invested_amount
+            .checked_sub(upgrade_cost)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        self.daily_rate = self.daily_rate.saturating_sub(rate_bonus);
+
+        self.last_upgrade_cost = 0;
+        self.last_upgrade_treasury_share = 0;
+        self.last_upgrade_rate_bonus = 0;
+        self.upgrade_level -= 1;
+        Ok(())
+    }
+
+    /// 🔁 Добавить реинвестированные earnings к базовому депозиту
+    pub fn add_principal(&mut self, amount: u64) -> Result<()> {
+        self.base_invested_amount = self.base_invested_amount
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.total_invested_amount = self.total_invested_amount
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// ✂️ Вывести часть базового депозита (partial sell).
+    /// Улучшения не трогаются - их снимает только downgrade; created_at не меняется.
+    pub fn withdraw_principal(&mut self, amount: u64) -> Result<()> {
+        if amount == 0 || amount > self.base_invested_amount {
+            return Err(ProgramError::InvalidArgument.into());
         }
 
-        let multiplier = UPGRADE_COST_MULTIPLIERS[(next_level - 1) as usize];
-        Some(self.base_invested_amount * multiplier as u64 / 100)
+        self.base_invested_amount -= amount;
+        self.total_invested_amount = self.total_invested_amount
+            .checked_sub(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// 🆕 Рассчитать стоимость следующего улучшения
+    pub fn calculate_next_upgrade_cost(&self, config: &GameConfig) -> Option<u64> {
+        config.get_upgrade_cost(self.base_invested_amount, self.upgrade_level + 1)
     }
 
     /// 🆕 Проверить возможность улучшения
-    pub fn can_upgrade(&self) -> bool {
-        self.upgrade_level < MAX_UPGRADE_LEVEL && self.is_active
+    pub fn can_upgrade(&self, config: &GameConfig) -> bool {
+        self.upgrade_level < config.max_upgrade_level && self.is_active
     }
 
     /// 🆕 Получить полную стоимость для возврата при продаже
@@ -182,45 +275,63 @@ impl Business {
     }
 
     /// Получить стоимость улучшения для определенного уровня
-    pub fn get_upgrade_cost(&self, target_level: u8) -> Result<u64> {
-        if target_level <= self.upgrade_level || target_level > MAX_UPGRADE_LEVEL {
+    pub fn get_upgrade_cost(&self, target_level: u8, config: &GameConfig) -> Result<u64> {
+        if target_level <= self.upgrade_level || target_level > config.max_upgrade_level {
             return Err(ProgramError::InvalidArgument.into());
         }
 
-        let multiplier = UPGRADE_COST_MULTIPLIERS[(target_level - 1) as usize];
-        let upgrade_cost = self.base_invested_amount
-            .checked_mul(multiplier as u64)
-            .and_then(|x| x.checked_div(100))
+        let upgrade_cost = config.get_upgrade_cost(self.base_invested_amount, target_level)
             .ok_or(ProgramError::ArithmeticOverflow)?;
         
         Ok(upgrade_cost)
     }
 
     /// Улучшить бизнес до определенного уровня
-    pub fn upgrade_to_level(&mut self, target_level: u8, upgrade_cost: u64) -> Result<()> {
-        if target_level != self.upgrade_level + 1 || target_level > MAX_UPGRADE_LEVEL {
+    /// `treasury_share` - часть upgrade_cost, которая осталась в treasury PDA
+    pub fn upgrade_to_level(&mut self, target_level: u8, upgrade_cost: u64, treasury_share: u64, config: &GameConfig) -> Result<()> {
+        if target_level != self.upgrade_level + 1 || target_level > config.max_upgrade_level {
             return Err(ProgramError::InvalidArgument.into());
         }
 
         // Проверяем корректность стоимости
-        let expected_cost = self.get_upgrade_cost(target_level)?;
+        let expected_cost = self.get_upgrade_cost(target_level, config)?;
         if upgrade_cost != expected_cost {
             return Err(ProgramError::InvalidArgument.into());
         }
 
         // Применяем улучшение
-        self.apply_upgrade(target_level, upgrade_cost)
+        self.apply_upgrade(target_level, upgrade_cost, treasury_share, config.get_upgrade_bonus(target_level))
     }
 
 
-    /// Calculate daily earnings with current rate
-    pub fn calculate_daily_earnings(&self) -> u64 {
-        let base_earnings = (self.total_invested_amount as u128)
+    /// 🆕 Доходный депозит: в RateBonus модели апгрейды дают ставку, а не депозит
+    pub fn earning_principal(&self) -> u64 {
+        match self.upgrade_model {
+            UpgradeModel::RateBonus => self.base_invested_amount,
+            UpgradeModel::Principal | UpgradeModel::Both => self.total_invested_amount,
+        }
+    }
+
+    /// Calculate daily earnings with current rate.
+    /// 📉 Депозит сверх каталожной цены доходит по убывающей кривой из GameConfig,
+    /// апгрейды масштабируются вместе с базой.
+    pub fn calculate_daily_earnings(&self, config: &GameConfig) -> u64 {
+        let catalog_price = self.business_type.get_base_cost(config);
+        let weighted_base = config.yield_weighted_principal(catalog_price, self.base_invested_amount);
+        let principal = self.earning_principal();
+
+        let weighted_total = if self.base_invested_amount == 0 {
+            principal as u128
+        } else {
+            (principal as u128)
+                .saturating_mul(weighted_base as u128)
+                / self.base_invested_amount as u128
+        };
+
+        weighted_total
             .checked_mul(self.daily_rate as u128)
             .and_then(|x| x.checked_div(10_000))
-            .unwrap_or(0) as u64;
-            
-        base_earnings
+            .unwrap_or(0) as u64
     }
 
     // ❌ УДАЛЕНО: calculate_pending_earnings - заменено на calculate_claimable_earnings
@@ -247,12 +358,12 @@ impl Business {
     }
 
     /// Calculate earnings for specific period
-    pub fn calculate_earnings_for_period(&self, seconds: i64) -> u64 {
+    pub fn calculate_earnings_for_period(&self, seconds: i64, config: &GameConfig) -> u64 {
         if !self.is_active || seconds <= 0 {
             return 0;
         }
         
-        let daily_earnings = self.calculate_daily_earnings();
+        let daily_earnings = self.calculate_daily_earnings(config);
         // Используем более точный расчет для избежания потери точности при малых числах
         let total_earnings = (daily_earnings as u128)
             .checked_mul(seconds as u128)
@@ -282,8 +393,8 @@ impl Business {
             }
         }
         
-        // Проверяем разумность доходности (максимум 100% в день = 10000 bp)
-        if self.daily_rate > 10000 {
+        // Проверяем разумность доходности (база + бонусы улучшений)
+        if self.daily_rate > MAX_UPGRADED_RATE_BPS {
             return Err(ProgramError::InvalidArgument.into());
         }
         
@@ -328,7 +439,7 @@ impl Business {
     }
 
     /// 🆕 Рассчитать earnings с момента последнего claim или покупки (для auto-claim)
-    pub fn calculate_claimable_earnings(&self, current_time: i64) -> u64 {
+    pub fn calculate_claimable_earnings(&self, current_time: i64, config: &GameConfig) -> u64 {
         let earnings_start = Self::u32_to_timestamp(self.get_earnings_start_time());
         let active_seconds = current_time - earnings_start;
         
@@ -336,16 +447,156 @@ impl Business {
             return 0;
         }
         
-        self.calculate_earnings_for_period(active_seconds)
+        self.calculate_earnings_for_period(active_seconds, config)
     }
 
-    /// 🆕 Рассчитать полную суточную доходность (для пользователей без auto-claim)
-    pub fn calculate_full_daily_earnings_if_active(&self) -> u64 {
+    /// 🆕 Разбить накопление с начала периода по окну auto-claim [auto_start, auto_end):
+    /// (секунды внутри окна, завершенные 24h периоды вне окна, новое время claim после выплаты).
+    /// До окна засчитываются только целые периоды, после окна незавершенный период сохраняется.
+    pub fn accrual_split(&self, current_time: i64, auto_window: (i64, i64)) -> (i64, u64, i64) {
+        let (auto_start, auto_end) = auto_window;
+        let earnings_start = Self::u32_to_timestamp(self.get_earnings_start_time());
+        if current_time <= earnings_start {
+            return (0, 0, earnings_start);
+        }
+
+        let auto_seconds = (current_time.min(auto_end) - earnings_start.max(auto_start)).max(0);
+
+        let pre_periods = if earnings_start < auto_start {
+            ((current_time.min(auto_start) - earnings_start) / EARNINGS_INTERVAL) as u64
+        } else {
+            0
+        };
+
+        let (post_periods, settled_at) = if current_time > auto_end {
+            let post_start = earnings_start.max(auto_end);
+            let periods = (current_time - post_start) / EARNINGS_INTERVAL;
+            (periods as u64, post_start + periods * EARNINGS_INTERVAL)
+        } else {
+            (0, current_time)
+        };
+
+        (auto_seconds, pre_periods + post_periods, settled_at)
+    }
+
+    /// 🆕 Завершенные 24h периоды вне окна auto-claim
+    pub fn completed_periods(&self, current_time: i64, auto_window: (i64, i64)) -> u64 {
+        self.accrual_split(current_time, auto_window).1
+    }
+
+    /// 🆕 Накопленные earnings: в окне auto-claim - точно по времени,
+    /// вне окна - завершенные 24h периоды (не больше max_accrual_days)
+    pub fn calculate_accrued_earnings(&self, current_time: i64, auto_window: (i64, i64), config: &GameConfig) -> u64 {
         if !self.is_active {
             return 0;
         }
-        
-        // Возвращаем полную суточную доходность независимо от времени
-        self.calculate_daily_earnings()
+
+        let (auto_seconds, periods, _) = self.accrual_split(current_time, auto_window);
+        let periods = periods.min(config.max_accrual_days as u64);
+        self.calculate_earnings_for_period(auto_seconds, config)
+            .saturating_add(self.calculate_daily_earnings(config).saturating_mul(periods))
     }
-}
\ No newline at end of file
+
+    /// 🆕 Зафиксировать claim: время claim сдвигается до конца выплаченного накопления
+    /// (периоды сверх max_accrual_days сгорают)
+    pub fn settle_claim(&mut self, current_time: i64, auto_window: (i64, i64)) {
+        let (_, _, settled_at) = self.accrual_split(current_time, auto_window);
+        if settled_at > Self::u32_to_timestamp(self.get_earnings_start_time()) {
+            self.update_claim_time(settled_at);
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START: i64 = 1_700_000_000;
+    const PRICE: u64 = 100_000_000;
+
+    fn business() -> Business {
+        Business::new(BusinessType::TobaccoShop, PRICE, 200, UpgradeModel::Both, START)
+    }
+
+    #[test]
+    fn accrual_is_capped_at_max_accrual_days() {
+        let config = GameConfig::new(Pubkey::new_unique(), 255);
+        let business = business();
+        let daily = business.calculate_daily_earnings(&config);
+        assert_eq!(daily, PRICE * 200 / 10_000);
+
+        let now = START + 10 * EARNINGS_INTERVAL + 100;
+        assert_eq!(business.completed_periods(now, (0, 0)), 10);
+        assert_eq!(
+            business.calculate_accrued_earnings(now, (0, 0), &config),
+            daily * config.max_accrual_days as u64
+        );
+    }
+
+    #[test]
+    fn settle_claim_burns_excess_periods_and_keeps_the_partial_one() {
+        let config = GameConfig::new(Pubkey::new_unique(), 255);
+        let mut business = business();
+
+        let now = START + 10 * EARNINGS_INTERVAL + 100;
+        business.settle_claim(now, (0, 0));
+        assert_eq!(business.last_claim_at, Some((START + 10 * EARNINGS_INTERVAL) as u32));
+        assert_eq!(business.calculate_accrued_earnings(now, (0, 0), &config), 0);
+        assert_eq!(
+            business.calculate_accrued_earnings(now + EARNINGS_INTERVAL - 100, (0, 0), &config),
+            business.calculate_daily_earnings(&config)
+        );
+    }
+
+    #[test]
+    fn auto_claim_window_accrues_by_the_second() {
+        let config = GameConfig::new(Pubkey::new_unique(), 255);
+        let business = business();
+
+        let now = START + EARNINGS_INTERVAL / 2;
+        let (auto_seconds, periods, settled_at) = business.accrual_split(now, (START, i64::MAX));
+        assert_eq!((auto_seconds, periods, settled_at), (EARNINGS_INTERVAL / 2, 0, now));
+        assert_eq!(
+            business.calculate_accrued_earnings(now, (START, i64::MAX), &config),
+            business.calculate_daily_earnings(&config) / 2
+        );
+    }
+
+    #[test]
+    fn downgrade_removes_exactly_the_last_upgrade_once() {
+        let mut business = business();
+        business.apply_upgrade(1, 20_000_000, 16_000_000, 10).unwrap();
+        assert_eq!(business.daily_rate, 210);
+
+        business.remove_top_upgrade().unwrap();
+        assert_eq!(business.daily_rate, 200);
+        assert_eq!(business.upgrade_level, 0);
+        assert_eq!(business.upgrade_spent, 0);
+        assert_eq!(business.total_invested_amount, PRICE);
+        assert!(!business.can_downgrade());
+    }
+
+    #[test]
+    fn only_one_downgrade_per_upgrade() {
+        let mut business = business();
+        business.apply_upgrade(1, 20_000_000, 16_000_000, 10).unwrap();
+        business.apply_upgrade(2, 50_000_000, 40_000_000, 25).unwrap();
+
+        business.remove_top_upgrade().unwrap();
+        assert_eq!(business.upgrade_level, 1);
+        assert_eq!(business.daily_rate, 210);
+        assert!(business.remove_top_upgrade().is_err());
+    }
+
+    #[test]
+    fn partial_withdraw_takes_only_base_principal() {
+        let mut business = business();
+        business.add_principal(PRICE).unwrap();
+        business.apply_upgrade(1, 20_000_000, 16_000_000, 10).unwrap();
+
+        business.withdraw_principal(PRICE).unwrap();
+        assert_eq!(business.base_invested_amount, PRICE);
+        assert_eq!(business.upgrade_spent, 20_000_000);
+        assert_eq!(business.total_invested_amount, PRICE + 20_000_000);
+        assert!(business.withdraw_principal(PRICE + 1).is_err());
+    }
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::error::SolanaMafiaError;
+
+/// 🤖 Разрешение keeper'у клеймить earnings за игрока (crank_claim).
+/// Earnings всегда уходят на кошелек игрока, keeper получает только tip.
+#[account]
+pub struct ClaimDelegate {
+    pub player: Pubkey,
+    pub keeper: Pubkey,
+    /// Tip keeper'а с чистой выплаты (bps, не больше game_config.max_keeper_tip_bps)
+    pub tip_bps: u16,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl ClaimDelegate {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // player
+        32 + // keeper
+        2 + // tip_bps
+        8 + // created_at
+        1; // bump
+
+    /// Прочитать ClaimDelegate из remaining accounts и проверить PDA игрока и keeper'а
+    pub fn load_checked(account: &AccountInfo, player: &Pubkey, keeper: &Pubkey) -> Result<Self> {
+        require_keys_eq!(*account.owner, crate::ID, SolanaMafiaError::InvalidClaimDelegate);
+        let delegate = {
+            let data = account.try_borrow_data()?;
+            Self::try_deserialize(&mut &data[..])?
+        };
+        let expected = Pubkey::create_program_address(
+            &[CLAIM_DELEGATE_SEED, player.as_ref(), &[delegate.bump]],
+            &crate::ID,
+        ).map_err(|_| SolanaMafiaError::InvalidClaimDelegate)?;
+        require_keys_eq!(account.key(), expected, SolanaMafiaError::InvalidClaimDelegate);
+        require_keys_eq!(delegate.player, *player, SolanaMafiaError::InvalidClaimDelegate);
+        require_keys_eq!(delegate.keeper, *keeper, SolanaMafiaError::UnauthorizedKeeper);
+        Ok(delegate)
+    }
+
+    /// Фактический tip: меньшее из согласованного игроком и текущего лимита конфига
+    pub fn effective_tip_bps(&self, max_keeper_tip_bps: u16) -> u16 {
+        self.tip_bps.min(max_keeper_tip_bps)
+    }
+}
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::error::SolanaMafiaError;
+
+/// Получатель доли командной комиссии
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct FeeRecipient {
+    pub wallet: Pubkey,
+    /// Доля в basis points (сумма всех долей = 10_000)
+    pub share_bps: u16,
+}
+
+impl FeeRecipient {
+    pub const SIZE: usize = 32 + 2;
+}
+
+/// 💸 Распределение командных комиссий между партнерами.
+/// Пока recipient_count == 0, все комиссии идут на game_state.treasury_wallet.
+#[account]
+pub struct FeeSplit {
+    pub recipients: [FeeRecipient; MAX_FEE_RECIPIENTS],
+    pub recipient_count: u8,
+    pub bump: u8,
+}
+
+impl FeeSplit {
+    pub const SIZE: usize = 8 + // discriminator
+        FeeRecipient::SIZE * MAX_FEE_RECIPIENTS + // recipients
+        1 + // recipient_count
+        1; // bump
+
+    /// Create empty split (everything goes to treasury wallet)
+    pub fn new(bump: u8) -> Self {
+        Self {
+            recipients: [FeeRecipient::default(); MAX_FEE_RECIPIENTS],
+            recipient_count: 0,
+            bump,
+        }
+    }
+
+    /// Split is configured
+    pub fn is_active(&self) -> bool {
+        self.recipient_count > 0
+    }
+
+    /// Configured recipients
+    pub fn active_recipients(&self) -> &[FeeRecipient] {
+        &self.recipients[..self.recipient_count as usize]
+    }
+
+    /// Replace recipients (empty list = back to treasury wallet)
+    pub fn set_recipients(&mut self, recipients: &[FeeRecipient]) -> Result<()> {
+        require!(
+            recipients.len() <= MAX_FEE_RECIPIENTS,
+            SolanaMafiaError::InvalidFeeSplit
+        );
+
+        if !recipients.is_empty() {
+            let total_bps: u32 = recipients.iter().map(|r| r.share_bps as u32).sum();
+            require!(total_bps == 10_000, SolanaMafiaError::InvalidFeeSplit);
+
+            for (i, recipient) in recipients.iter().enumerate() {
+                require!(
+                    recipient.wallet != Pubkey::default() && recipient.share_bps > 0,
+                    SolanaMafiaError::InvalidFeeSplit
+                );
+                require!(
+                    !recipients[..i].iter().any(|r| r.wallet == recipient.wallet),
+                    SolanaMafiaError::InvalidFeeSplit
+                );
+            }
+        }
+
+        self.recipients = [FeeRecipient::default(); MAX_FEE_RECIPIENTS];
+        self.recipients[..recipients.len()].copy_from_slice(recipients);
+        self.recipient_count = recipients.len() as u8;
+        Ok(())
+    }
+
+    /// Split amount by shares; rounding dust goes to the first recipient
+    pub fn split_amount(&self, amount: u64) -> [u64; MAX_FEE_RECIPIENTS] {
+        let mut shares = [0u64; MAX_FEE_RECIPIENTS];
+        let mut distributed = 0u64;
+
+        for (share, recipient) in shares.iter_mut().zip(self.active_recipients()) {
+            *share = (amount as u128 * recipient.share_bps as u128 / 10_000) as u64;
+            distributed += *share;
+        }
+
+        if self.is_active() {
+            shares[0] += amount - distributed;
+        }
+        shares
+    }
+}
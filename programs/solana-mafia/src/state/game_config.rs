@@ -2,6 +2,31 @@
 use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::error::SolanaMafiaError;
+use crate::state::pending_config_change::ConfigChange;
+
+/// Параметр GameConfig, измененный админом (для события ConfigUpdated)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ConfigParameter {
+    EntryFee,
+    BusinessRates,
+    MinDeposits,
+    UpgradeSchedule,
+    FeeSplit,
+    SlotCosts,
+    SellFees,
+    AutoClaimTiers,
+    RegistrationsOpen,
+    TreasuryChangeDelay,
+    ConfigTimelock,
+    ProRataMaxDraw,
+    ReferralProgram,
+    DepositTiers,
+    YieldCurve,
+    UpgradeModels,
+    ReinvestFee,
+    MaxAccrualDays,
+    MaxKeeperTip,
+}
 
 #[account]
 pub struct GameConfig {
@@ -29,8 +54,8 @@ pub struct GameConfig {
     /// Treasury fee percentage (what goes to team)
     pub treasury_fee_percent: u8,
     
-    /// Upgrade costs for each level
-    pub upgrade_costs: [u64; MAX_UPGRADE_LEVEL as usize],
+    /// Upgrade cost multipliers for each level (% of business base cost)
+    pub upgrade_cost_multipliers: [u16; MAX_UPGRADE_LEVEL as usize],
     
     /// Upgrade bonuses for each level (basis points)
     pub upgrade_bonuses: [u16; MAX_UPGRADE_LEVEL as usize],
@@ -45,8 +70,83 @@ pub struct GameConfig {
     /// Current entry fee in lamports (controlled by backend)
     pub current_entry_fee: u64,
     
+    /// Claim fee percentage (what goes to team from each claim)
+    pub claim_fee_percent: u8,
+    
+    /// Auto claim subscription price per tier in lamports
+    pub auto_claim_tier_costs: [u64; AUTO_CLAIM_TIERS],
+    
+    /// Auto claim subscription length per tier in days
+    pub auto_claim_tier_days: [u16; AUTO_CLAIM_TIERS],
+    
+    /// Cost of paid basic slots (% of business price in slot)
+    pub slot_unlock_cost_percent: u8,
+    
+    /// Premium/VIP/Legendary slot costs in lamports
+    pub premium_slot_costs: [u64; 3],
+    
+    /// Premium/VIP/Legendary slot yield bonuses (basis points)
+    pub premium_slot_yield_bonuses: [u16; 3],
+    
+    /// Premium/VIP/Legendary slot sell fee discounts (percentage points)
+    pub premium_slot_sell_fee_discounts: [u8; 3],
+    
+    /// Early sell fees by days held (last entry applies to all later days)
+    pub early_sell_fees: [u8; EARLY_SELL_FEES_LEN],
+    
     /// Bump seed for PDA
     pub bump: u8,
+    
+    /// Proposed new authority (Pubkey::default() = no pending transfer)
+    pub pending_authority: Pubkey,
+    
+    /// Minimum delay between queueing and executing a config change (seconds)
+    pub config_timelock: i64,
+    
+    /// Id for the next PendingConfigChange PDA
+    pub next_config_change_id: u64,
+    
+    /// Sensitive admin actions require an approved AdminCouncil proposal
+    pub council_enabled: bool,
+    
+    /// Max share of free treasury reserves a single payout may take in pro-rata mode (basis points)
+    pub pro_rata_max_draw_bps: u16,
+    
+    /// How many referrer levels receive a bonus (0 = referral program off)
+    pub referral_depth: u8,
+    
+    /// Referral bonus per level (basis points of the team fee)
+    pub referral_bonus_bps: [u16; MAX_REFERRAL_DEPTH],
+    
+    /// create_business accepts only deposit_tiers prices (false = any deposit >= min)
+    pub strict_pricing: bool,
+    
+    /// Allowed deposits in strict mode (% of catalog price, 0 = unused)
+    pub deposit_tiers: [u16; MAX_DEPOSIT_TIERS],
+    
+    /// Max deposit (business principal) for each business type
+    pub max_deposits: [u64; BUSINESS_TYPES_COUNT],
+    
+    /// Rate drop per extra catalog price of principal (percent of base rate)
+    pub yield_decay_percent: u8,
+    
+    /// Lowest rate the extra principal can earn (percent of base rate)
+    pub yield_floor_percent: u8,
+    
+    /// What an upgrade adds for each business type (principal, rate bonus or both)
+    pub upgrade_models: [UpgradeModel; BUSINESS_TYPES_COUNT],
+    
+    /// Number of upgrade levels in use (<= MAX_UPGRADE_LEVEL), shared by all upgrade paths
+    pub max_upgrade_level: u8,
+    
+    /// Fee on reinvested earnings (percent, stays in the treasury)
+    pub reinvest_fee_percent: u8,
+    
+    /// Completed 24h periods a player without auto-claim can accumulate per business
+    pub max_accrual_days: u16,
+    
+    /// Max tip (bps of the net payout) a player can grant a keeper for delegated claims
+    pub max_keeper_tip_bps: u16,
 }
 
 impl GameConfig {
@@ -60,12 +160,37 @@ impl GameConfig {
         8 + // fee_increment
         8 + // players_per_milestone
         1 + // treasury_fee_percent
-        8 * (MAX_UPGRADE_LEVEL as usize) + // upgrade_costs
+        2 * (MAX_UPGRADE_LEVEL as usize) + // upgrade_cost_multipliers
         2 * (MAX_UPGRADE_LEVEL as usize) + // upgrade_bonuses
         1 + // max_businesses_per_player
         1 + // registrations_open
         8 + // current_entry_fee
-        1; // bump
+        1 + // claim_fee_percent
+        8 * AUTO_CLAIM_TIERS + // auto_claim_tier_costs
+        2 * AUTO_CLAIM_TIERS + // auto_claim_tier_days
+        1 + // slot_unlock_cost_percent
+        8 * 3 + // premium_slot_costs
+        2 * 3 + // premium_slot_yield_bonuses
+        3 + // premium_slot_sell_fee_discounts
+        EARLY_SELL_FEES_LEN + // early_sell_fees
+        1 + // bump
+        32 + // pending_authority
+        8 + // config_timelock
+        8 + // next_config_change_id
+        1 + // council_enabled
+        2 + // pro_rata_max_draw_bps
+        1 + // referral_depth
+        2 * MAX_REFERRAL_DEPTH + // referral_bonus_bps
+        1 + // strict_pricing
+        2 * MAX_DEPOSIT_TIERS + // deposit_tiers
+        8 * BUSINESS_TYPES_COUNT + // max_deposits
+        1 + // yield_decay_percent
+        1 + // yield_floor_percent
+        BUSINESS_TYPES_COUNT + // upgrade_models
+        1 + // max_upgrade_level
+        1 + // reinvest_fee_percent
+        2 + // max_accrual_days
+        2; // max_keeper_tip_bps
 
     /// Create new config with default values
     pub fn new(authority: Pubkey, bump: u8) -> Self {
@@ -78,15 +203,56 @@ impl GameConfig {
             fee_increment: FEE_INCREMENT,
             players_per_milestone: PLAYERS_PER_MILESTONE,
             treasury_fee_percent: TREASURY_FEE_PERCENT,
-            upgrade_costs: UPGRADE_COSTS,
+            upgrade_cost_multipliers: UPGRADE_COST_MULTIPLIERS,
             upgrade_bonuses: UPGRADE_BONUSES,
             max_businesses_per_player: MAX_BUSINESSES_PER_PLAYER,
             registrations_open: true,
             current_entry_fee: INITIAL_ENTRY_FEE, // Start with initial fee, backend will control
+            claim_fee_percent: CLAIM_EARNINGS_FEE_PERCENT,
+            auto_claim_tier_costs: AUTO_CLAIM_TIER_COSTS,
+            auto_claim_tier_days: AUTO_CLAIM_TIER_DAYS,
+            slot_unlock_cost_percent: SLOT_UNLOCK_COST_MULTIPLIER,
+            premium_slot_costs: PREMIUM_SLOT_COSTS,
+            premium_slot_yield_bonuses: PREMIUM_SLOT_YIELD_BONUSES,
+            premium_slot_sell_fee_discounts: PREMIUM_SLOT_SELL_FEE_DISCOUNTS,
+            early_sell_fees: EARLY_SELL_FEES,
             bump,
+            pending_authority: Pubkey::default(),
+            config_timelock: DEFAULT_CONFIG_TIMELOCK,
+            next_config_change_id: 0,
+            council_enabled: false,
+            pro_rata_max_draw_bps: DEFAULT_PRO_RATA_MAX_DRAW_BPS,
+            referral_depth: MAX_REFERRAL_DEPTH as u8,
+            referral_bonus_bps: DEFAULT_REFERRAL_BONUS_BPS,
+            strict_pricing: false, // Любой депозит от min до max_deposit; строгий режим включается через timelock
+            deposit_tiers: DEFAULT_DEPOSIT_TIERS,
+            max_deposits: MAX_DEPOSITS,
+            yield_decay_percent: YIELD_DECAY_PERCENT,
+            yield_floor_percent: YIELD_FLOOR_PERCENT,
+            upgrade_models: UPGRADE_MODELS,
+            max_upgrade_level: DEFAULT_MAX_UPGRADE_LEVEL,
+            reinvest_fee_percent: REINVEST_FEE_PERCENT,
+            max_accrual_days: DEFAULT_MAX_ACCRUAL_DAYS,
+            max_keeper_tip_bps: DEFAULT_MAX_KEEPER_TIP_BPS,
         }
     }
 
+    /// Config is frozen forever once authority is renounced
+    pub fn is_frozen(&self) -> bool {
+        self.authority == Pubkey::default()
+    }
+
+    /// Check that signer is the current authority
+    pub fn require_authority(&self, signer: &Pubkey) -> Result<()> {
+        if self.is_frozen() {
+            return Err(SolanaMafiaError::ConfigFrozen.into());
+        }
+        if *signer != self.authority {
+            return Err(SolanaMafiaError::UnauthorizedAdmin.into());
+        }
+        Ok(())
+    }
+
     /// Get daily rate for business type
     pub fn get_business_rate(&self, business_type_index: usize) -> u16 {
         if business_type_index < BUSINESS_TYPES_COUNT {
@@ -105,24 +271,160 @@ impl GameConfig {
         }
     }
 
-    /// Get upgrade cost for level
-    pub fn get_upgrade_cost(&self, level: u8) -> Option<u64> {
-        if level > 0 && level <= MAX_UPGRADE_LEVEL {
-            Some(self.upgrade_costs[(level - 1) as usize])
+    /// Get max deposit for business type
+    pub fn get_max_deposit(&self, business_type_index: usize) -> u64 {
+        if business_type_index < BUSINESS_TYPES_COUNT {
+            self.max_deposits[business_type_index]
+        } else {
+            0
+        }
+    }
+
+    /// 📉 Principal weighted by the yield curve.
+    /// The first catalog price earns the full rate, every next catalog price earns
+    /// yield_decay_percent less, never below yield_floor_percent.
+    pub fn yield_weighted_principal(&self, catalog_price: u64, principal: u64) -> u64 {
+        // Без decay весь депозит идет по полной ставке
+        if catalog_price == 0 || principal <= catalog_price || self.yield_decay_percent == 0 {
+            return principal;
+        }
+
+        let mut weighted = catalog_price as u128;
+        let mut remaining = (principal - catalog_price) as u128;
+        let mut percent = 100u128;
+
+        // Убывающие куски: decay >= 1, поэтому не больше 100 итераций, дальше все по floor
+        while remaining > 0 {
+            percent = percent
+                .saturating_sub(self.yield_decay_percent as u128)
+                .max(self.yield_floor_percent as u128);
+            if percent == self.yield_floor_percent as u128 {
+                weighted += remaining * percent / 100;
+                break;
+            }
+            let chunk = remaining.min(catalog_price as u128);
+            weighted += chunk * percent / 100;
+            remaining -= chunk;
+        }
+
+        weighted.min(principal as u128) as u64
+    }
+
+    /// Update max deposits and yield curve (admin only)
+    pub fn set_yield_curve(
+        &mut self,
+        max_deposits: [u64; BUSINESS_TYPES_COUNT],
+        decay_percent: u8,
+        floor_percent: u8,
+    ) -> Result<()> {
+        require!(
+            max_deposits.iter().zip(self.min_deposits.iter()).all(|(&max, &min)| {
+                max >= min && (max as u128) <= min as u128 * MAX_DEPOSIT_TIER_PERCENT as u128 / 100
+            }),
+            SolanaMafiaError::InvalidYieldCurve
+        );
+        require!(decay_percent <= 100 && floor_percent <= 100, SolanaMafiaError::InvalidYieldCurve);
+        self.max_deposits = max_deposits;
+        self.yield_decay_percent = decay_percent;
+        self.yield_floor_percent = floor_percent;
+        Ok(())
+    }
+
+    /// Get upgrade cost for level (multiplier applied to business base cost)
+    pub fn get_upgrade_cost(&self, base_cost: u64, level: u8) -> Option<u64> {
+        if level > 0 && level <= self.max_upgrade_level {
+            let multiplier = self.upgrade_cost_multipliers[(level - 1) as usize];
+            base_cost
+                .checked_mul(multiplier as u64)
+                .and_then(|x| x.checked_div(100))
         } else {
             None
         }
     }
 
+    /// Get upgrade model for business type
+    pub fn get_upgrade_model(&self, business_type_index: usize) -> UpgradeModel {
+        if business_type_index < BUSINESS_TYPES_COUNT {
+            self.upgrade_models[business_type_index]
+        } else {
+            UpgradeModel::Principal
+        }
+    }
+
+    /// Highest base rate plus all upgrade bonuses must stay within MAX_UPGRADED_RATE_BPS
+    fn upgraded_rate_within_cap(rates: &[u16], bonuses: &[u16]) -> bool {
+        let max_rate = rates.iter().copied().max().unwrap_or(0) as u32;
+        let total_bonus: u32 = bonuses.iter().map(|&b| b as u32).sum();
+        max_rate + total_bonus <= MAX_UPGRADED_RATE_BPS as u32
+    }
+
+    /// Update upgrade models per business type (admin only)
+    pub fn set_upgrade_models(&mut self, models: [UpgradeModel; BUSINESS_TYPES_COUNT]) {
+        self.upgrade_models = models;
+    }
+
     /// Get upgrade bonus for level
     pub fn get_upgrade_bonus(&self, level: u8) -> u16 {
-        if level > 0 && level <= MAX_UPGRADE_LEVEL {
+        if level > 0 && level <= self.max_upgrade_level {
             self.upgrade_bonuses[(level - 1) as usize]
         } else {
             0
         }
     }
 
+    /// Split a payment into (team fee, treasury amount) using treasury_fee_percent
+    pub fn split_team_fee(&self, amount: u64) -> Result<(u64, u64)> {
+        let team_fee = amount
+            .checked_mul(self.treasury_fee_percent as u64)
+            .ok_or(SolanaMafiaError::MathOverflow)?
+            .checked_div(100)
+            .ok_or(SolanaMafiaError::MathOverflow)?;
+        let treasury_amount = amount
+            .checked_sub(team_fee)
+            .ok_or(SolanaMafiaError::InsufficientDeposit)?;
+        Ok((team_fee, treasury_amount))
+    }
+
+    /// Calculate claim fee for claimed earnings
+    pub fn calculate_claim_fee(&self, claimed_amount: u64) -> u64 {
+        (claimed_amount as u128 * self.claim_fee_percent as u128 / 100) as u64
+    }
+
+    /// Get slot cost for first use (paid/free status is checked by the slot itself)
+    pub fn get_slot_cost(&self, slot_type: SlotType, business_price: u64) -> u64 {
+        match slot_type {
+            SlotType::Basic => business_price * self.slot_unlock_cost_percent as u64 / 100,
+            SlotType::Premium => self.premium_slot_costs[0],
+            SlotType::VIP => self.premium_slot_costs[1],
+            SlotType::Legendary => self.premium_slot_costs[2],
+        }
+    }
+
+    /// Get slot yield bonus (basis points)
+    pub fn get_slot_yield_bonus(&self, slot_type: SlotType) -> u16 {
+        match slot_type {
+            SlotType::Basic => 0,
+            SlotType::Premium => self.premium_slot_yield_bonuses[0],
+            SlotType::VIP => self.premium_slot_yield_bonuses[1],
+            SlotType::Legendary => self.premium_slot_yield_bonuses[2],
+        }
+    }
+
+    /// Get slot sell fee discount (percentage points)
+    pub fn get_slot_sell_fee_discount(&self, slot_type: SlotType) -> u8 {
+        match slot_type {
+            SlotType::Basic => 0,
+            SlotType::Premium => self.premium_slot_sell_fee_discounts[0],
+            SlotType::VIP => self.premium_slot_sell_fee_discounts[1],
+            SlotType::Legendary => self.premium_slot_sell_fee_discounts[2],
+        }
+    }
+
+    /// Get early sell fee percent by days held
+    pub fn get_sell_fee_percent(&self, days_held: u64) -> u8 {
+        let index = std::cmp::min(days_held as usize, EARLY_SELL_FEES_LEN - 1);
+        self.early_sell_fees[index]
+    }
 
     /// Get current entry fee with FOMO calculation based on total players
     pub fn get_current_entry_fee(&self, total_players: u64) -> u64 {
@@ -167,4 +469,409 @@ impl GameConfig {
         self.current_entry_fee = new_fee_lamports;
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// Update business daily rates (admin only)
+    pub fn set_business_rates(&mut self, rates: [u16; BUSINESS_TYPES_COUNT]) -> Result<()> {
+        require!(
+            rates.iter().all(|&rate| rate > 0 && rate <= MAX_BUSINESS_RATE_BPS)
+                && Self::upgraded_rate_within_cap(&rates, &self.upgrade_bonuses),
+            SolanaMafiaError::InvalidBusinessRate
+        );
+        self.business_rates = rates;
+        Ok(())
+    }
+
+    /// Update business minimum deposits (admin only)
+    pub fn set_min_deposits(&mut self, deposits: [u64; BUSINESS_TYPES_COUNT]) -> Result<()> {
+        require!(
+            deposits.iter().all(|&deposit| (MIN_DEPOSIT_FLOOR..=MAX_MIN_DEPOSIT).contains(&deposit)),
+            SolanaMafiaError::InvalidMinDeposit
+        );
+        require!(
+            deposits.iter().zip(self.max_deposits.iter()).all(|(&min, &max)| min <= max),
+            SolanaMafiaError::InvalidMinDeposit
+        );
+        self.min_deposits = deposits;
+        Ok(())
+    }
+
+    /// Update number of upgrade levels, cost multipliers and yield bonuses (admin only).
+    /// Entries past max_level must be zero.
+    pub fn set_upgrade_schedule(
+        &mut self,
+        max_level: u8,
+        cost_multipliers: [u16; MAX_UPGRADE_LEVEL as usize],
+        bonuses: [u16; MAX_UPGRADE_LEVEL as usize],
+    ) -> Result<()> {
+        require!(
+            max_level > 0 && max_level <= MAX_UPGRADE_LEVEL,
+            SolanaMafiaError::InvalidUpgradeSchedule
+        );
+        let (active_multipliers, unused_multipliers) = cost_multipliers.split_at(max_level as usize);
+        let (active_bonuses, unused_bonuses) = bonuses.split_at(max_level as usize);
+        require!(
+            active_multipliers.iter().all(|&m| m > 0 && m <= MAX_UPGRADE_COST_MULTIPLIER)
+                && unused_multipliers.iter().all(|&m| m == 0),
+            SolanaMafiaError::InvalidUpgradeSchedule
+        );
+        require!(
+            active_bonuses.iter().all(|&b| b <= MAX_UPGRADE_BONUS_BPS)
+                && unused_bonuses.iter().all(|&b| b == 0)
+                && Self::upgraded_rate_within_cap(&self.business_rates, &bonuses),
+            SolanaMafiaError::InvalidUpgradeSchedule
+        );
+        self.max_upgrade_level = max_level;
+        self.upgrade_cost_multipliers = cost_multipliers;
+        self.upgrade_bonuses = bonuses;
+        Ok(())
+    }
+
+    /// Update team fee and claim fee percentages (admin only)
+    pub fn set_fee_split(&mut self, treasury_fee_percent: u8, claim_fee_percent: u8) -> Result<()> {
+        require!(
+            treasury_fee_percent <= MAX_TREASURY_FEE_PERCENT,
+            SolanaMafiaError::InvalidFeePercentage
+        );
+        require!(
+            claim_fee_percent <= MAX_CLAIM_FEE_PERCENT,
+            SolanaMafiaError::InvalidFeePercentage
+        );
+        self.treasury_fee_percent = treasury_fee_percent;
+        self.claim_fee_percent = claim_fee_percent;
+        Ok(())
+    }
+
+    /// Update reinvest fee percentage (admin only)
+    pub fn set_reinvest_fee(&mut self, reinvest_fee_percent: u8) -> Result<()> {
+        require!(
+            reinvest_fee_percent <= MAX_REINVEST_FEE_PERCENT,
+            SolanaMafiaError::InvalidFeePercentage
+        );
+        self.reinvest_fee_percent = reinvest_fee_percent;
+        Ok(())
+    }
+
+    /// Update max stored accrual days for players without auto-claim (admin only)
+    pub fn set_max_accrual_days(&mut self, days: u16) -> Result<()> {
+        require!(
+            days > 0 && days <= MAX_ACCRUAL_DAYS_LIMIT,
+            SolanaMafiaError::InvalidMaxAccrualDays
+        );
+        self.max_accrual_days = days;
+        Ok(())
+    }
+
+    /// Update max keeper tip for delegated claims (admin only)
+    pub fn set_max_keeper_tip(&mut self, max_tip_bps: u16) -> Result<()> {
+        require!(max_tip_bps <= MAX_KEEPER_TIP_BPS, SolanaMafiaError::InvalidKeeperTip);
+        self.max_keeper_tip_bps = max_tip_bps;
+        Ok(())
+    }
+
+    /// Update slot costs and premium slot perks (admin only)
+    pub fn set_slot_costs(
+        &mut self,
+        slot_unlock_cost_percent: u8,
+        premium_slot_costs: [u64; 3],
+        premium_slot_yield_bonuses: [u16; 3],
+        premium_slot_sell_fee_discounts: [u8; 3],
+    ) -> Result<()> {
+        require!(
+            slot_unlock_cost_percent <= MAX_SLOT_UNLOCK_COST_PERCENT,
+            SolanaMafiaError::InvalidSlotConfig
+        );
+        require!(
+            premium_slot_costs.iter().all(|&cost| cost <= MAX_PREMIUM_SLOT_COST),
+            SolanaMafiaError::InvalidSlotConfig
+        );
+        require!(
+            premium_slot_yield_bonuses.iter().all(|&b| b <= MAX_SLOT_YIELD_BONUS_BPS),
+            SolanaMafiaError::InvalidSlotConfig
+        );
+        require!(
+            premium_slot_sell_fee_discounts.iter().all(|&d| d <= 100),
+            SolanaMafiaError::InvalidFeePercentage
+        );
+        self.slot_unlock_cost_percent = slot_unlock_cost_percent;
+        self.premium_slot_costs = premium_slot_costs;
+        self.premium_slot_yield_bonuses = premium_slot_yield_bonuses;
+        self.premium_slot_sell_fee_discounts = premium_slot_sell_fee_discounts;
+        Ok(())
+    }
+
+    /// Update early sell fee table (admin only)
+    pub fn set_early_sell_fees(&mut self, fees: [u8; EARLY_SELL_FEES_LEN]) -> Result<()> {
+        require!(
+            fees.iter().all(|&fee| fee <= MAX_SELL_FEE_PERCENT),
+            SolanaMafiaError::InvalidFeePercentage
+        );
+        self.early_sell_fees = fees;
+        Ok(())
+    }
+
+    /// Get auto claim tier (cost, days)
+    pub fn get_auto_claim_tier(&self, tier: u8) -> Option<(u64, u16)> {
+        let tier = tier as usize;
+        if tier < AUTO_CLAIM_TIERS {
+            Some((self.auto_claim_tier_costs[tier], self.auto_claim_tier_days[tier]))
+        } else {
+            None
+        }
+    }
+
+    /// Update auto claim subscription tiers (admin only)
+    pub fn set_auto_claim_tiers(&mut self, days: [u16; AUTO_CLAIM_TIERS], costs: [u64; AUTO_CLAIM_TIERS]) -> Result<()> {
+        require!(
+            days.iter().all(|&d| d > 0 && d <= MAX_AUTO_CLAIM_DAYS)
+                && days.windows(2).all(|pair| pair[0] < pair[1])
+                && costs.iter().all(|&cost| cost > 0 && cost <= MAX_AUTO_CLAIM_COST),
+            SolanaMafiaError::InvalidAutoClaimCost
+        );
+        self.auto_claim_tier_days = days;
+        self.auto_claim_tier_costs = costs;
+        Ok(())
+    }
+
+    /// Update pro-rata draw limit
+    pub fn set_pro_rata_max_draw(&mut self, bps: u16) -> Result<()> {
+        require!(
+            bps > 0 && bps <= 10_000,
+            SolanaMafiaError::InvalidProRataDraw
+        );
+        self.pro_rata_max_draw_bps = bps;
+        Ok(())
+    }
+
+    /// Update referral program depth and per-level bonuses
+    pub fn set_referral_program(&mut self, depth: u8, bonus_bps: [u16; MAX_REFERRAL_DEPTH]) -> Result<()> {
+        let total_bps: u32 = bonus_bps.iter().take(depth as usize).map(|&b| b as u32).sum();
+        require!(
+            depth as usize <= MAX_REFERRAL_DEPTH && total_bps <= MAX_REFERRAL_TOTAL_BPS as u32,
+            SolanaMafiaError::InvalidReferralConfig
+        );
+        self.referral_depth = depth;
+        self.referral_bonus_bps = bonus_bps;
+        Ok(())
+    }
+
+    /// Check deposit against allowed tiers (strict pricing mode)
+    pub fn is_allowed_deposit(&self, catalog_price: u64, deposit_amount: u64) -> bool {
+        if !self.strict_pricing {
+            return true;
+        }
+        self.deposit_tiers.iter()
+            .filter(|&&tier| tier > 0)
+            .any(|&tier| (catalog_price as u128 * tier as u128 / 100) == deposit_amount as u128)
+    }
+
+    /// Update strict pricing mode and deposit tiers
+    pub fn set_deposit_tiers(&mut self, strict_pricing: bool, tiers: [u16; MAX_DEPOSIT_TIERS]) -> Result<()> {
+        let active = tiers.iter().take_while(|&&tier| tier > 0).count();
+        require!(
+            active > 0
+                && tiers[active..].iter().all(|&tier| tier == 0)
+                && tiers[0] >= 100
+                && tiers[active - 1] <= MAX_DEPOSIT_TIER_PERCENT
+                && tiers[..active].windows(2).all(|pair| pair[0] < pair[1]),
+            SolanaMafiaError::InvalidDepositTiers
+        );
+        self.strict_pricing = strict_pricing;
+        self.deposit_tiers = tiers;
+        Ok(())
+    }
+
+    /// Update config change timelock
+    pub fn set_config_timelock(&mut self, delay: i64) -> Result<()> {
+        require!(
+            (MIN_CONFIG_TIMELOCK..=MAX_CONFIG_TIMELOCK).contains(&delay),
+            SolanaMafiaError::InvalidTimelockDelay
+        );
+        self.config_timelock = delay;
+        Ok(())
+    }
+
+    /// Validate and apply a config change, returns (parameter, old values, new values)
+    /// Multi-value parameters are flattened in field declaration order.
+    pub fn apply_change(&mut self, change: &ConfigChange) -> Result<(ConfigParameter, Vec<u64>, Vec<u64>)> {
+        let result = match change.clone() {
+            ConfigChange::EntryFee(fee) => {
+                let old = vec![self.current_entry_fee];
+                self.update_entry_fee(fee)?;
+                (ConfigParameter::EntryFee, old, vec![fee])
+            }
+            ConfigChange::BusinessRates(rates) => {
+                let old = self.business_rates.iter().map(|&r| r as u64).collect();
+                self.set_business_rates(rates)?;
+                (ConfigParameter::BusinessRates, old, rates.iter().map(|&r| r as u64).collect())
+            }
+            ConfigChange::MinDeposits(deposits) => {
+                let old = self.min_deposits.to_vec();
+                self.set_min_deposits(deposits)?;
+                (ConfigParameter::MinDeposits, old, deposits.to_vec())
+            }
+            ConfigChange::UpgradeSchedule { max_level, cost_multipliers, bonuses } => {
+                let old = std::iter::once(self.max_upgrade_level as u64)
+                    .chain(self.upgrade_cost_multipliers.iter().chain(self.upgrade_bonuses.iter()).map(|&v| v as u64))
+                    .collect();
+                self.set_upgrade_schedule(max_level, cost_multipliers, bonuses)?;
+                let new = std::iter::once(max_level as u64)
+                    .chain(cost_multipliers.iter().chain(bonuses.iter()).map(|&v| v as u64))
+                    .collect();
+                (ConfigParameter::UpgradeSchedule, old, new)
+            }
+            ConfigChange::FeeSplit { treasury_fee_percent, claim_fee_percent } => {
+                let old = vec![self.treasury_fee_percent as u64, self.claim_fee_percent as u64];
+                self.set_fee_split(treasury_fee_percent, claim_fee_percent)?;
+                (ConfigParameter::FeeSplit, old, vec![treasury_fee_percent as u64, claim_fee_percent as u64])
+            }
+            ConfigChange::SlotCosts {
+                slot_unlock_cost_percent,
+                premium_slot_costs,
+                premium_slot_yield_bonuses,
+                premium_slot_sell_fee_discounts,
+            } => {
+                let flatten = |percent: u8, costs: &[u64; 3], bonuses: &[u16; 3], discounts: &[u8; 3]| -> Vec<u64> {
+                    std::iter::once(percent as u64)
+                        .chain(costs.iter().copied())
+                        .chain(bonuses.iter().map(|&b| b as u64))
+                        .chain(discounts.iter().map(|&d| d as u64))
+                        .collect()
+                };
+                let old = flatten(
+                    self.slot_unlock_cost_percent,
+                    &self.premium_slot_costs,
+                    &self.premium_slot_yield_bonuses,
+                    &self.premium_slot_sell_fee_discounts,
+                );
+                self.set_slot_costs(
+                    slot_unlock_cost_percent,
+                    premium_slot_costs,
+                    premium_slot_yield_bonuses,
+                    premium_slot_sell_fee_discounts,
+                )?;
+                let new = flatten(
+                    slot_unlock_cost_percent,
+                    &premium_slot_costs,
+                    &premium_slot_yield_bonuses,
+                    &premium_slot_sell_fee_discounts,
+                );
+                (ConfigParameter::SlotCosts, old, new)
+            }
+            ConfigChange::SellFees(fees) => {
+                let old = self.early_sell_fees.iter().map(|&f| f as u64).collect();
+                self.set_early_sell_fees(fees)?;
+                (ConfigParameter::SellFees, old, fees.iter().map(|&f| f as u64).collect())
+            }
+            ConfigChange::AutoClaimTiers { days, costs } => {
+                let old = self.auto_claim_tier_days.iter().map(|&d| d as u64)
+                    .chain(self.auto_claim_tier_costs.iter().copied())
+                    .collect();
+                self.set_auto_claim_tiers(days, costs)?;
+                let new = days.iter().map(|&d| d as u64).chain(costs.iter().copied()).collect();
+                (ConfigParameter::AutoClaimTiers, old, new)
+            }
+            ConfigChange::RegistrationsOpen(open) => {
+                let old = vec![self.registrations_open as u64];
+                self.registrations_open = open;
+                (ConfigParameter::RegistrationsOpen, old, vec![open as u64])
+            }
+            ConfigChange::ConfigTimelock(delay) => {
+                let old = vec![self.config_timelock as u64];
+                self.set_config_timelock(delay)?;
+                (ConfigParameter::ConfigTimelock, old, vec![delay as u64])
+            }
+            ConfigChange::ProRataMaxDraw(bps) => {
+                let old = vec![self.pro_rata_max_draw_bps as u64];
+                self.set_pro_rata_max_draw(bps)?;
+                (ConfigParameter::ProRataMaxDraw, old, vec![bps as u64])
+            }
+            ConfigChange::ReferralProgram { depth, bonus_bps } => {
+                let old = std::iter::once(self.referral_depth as u64)
+                    .chain(self.referral_bonus_bps.iter().map(|&b| b as u64))
+                    .collect();
+                self.set_referral_program(depth, bonus_bps)?;
+                let new = std::iter::once(depth as u64)
+                    .chain(bonus_bps.iter().map(|&b| b as u64))
+                    .collect();
+                (ConfigParameter::ReferralProgram, old, new)
+            }
+            ConfigChange::DepositTiers { strict_pricing, tiers } => {
+                let old = std::iter::once(self.strict_pricing as u64)
+                    .chain(self.deposit_tiers.iter().map(|&t| t as u64))
+                    .collect();
+                self.set_deposit_tiers(strict_pricing, tiers)?;
+                let new = std::iter::once(strict_pricing as u64)
+                    .chain(tiers.iter().map(|&t| t as u64))
+                    .collect();
+                (ConfigParameter::DepositTiers, old, new)
+            }
+            ConfigChange::YieldCurve { max_deposits, decay_percent, floor_percent } => {
+                let old = self.max_deposits.iter().copied()
+                    .chain([self.yield_decay_percent as u64, self.yield_floor_percent as u64])
+                    .collect();
+                self.set_yield_curve(max_deposits, decay_percent, floor_percent)?;
+                let new = max_deposits.iter().copied()
+                    .chain([decay_percent as u64, floor_percent as u64])
+                    .collect();
+                (ConfigParameter::YieldCurve, old, new)
+            }
+            ConfigChange::UpgradeModels(models) => {
+                let old = self.upgrade_models.iter().map(|&m| m as u64).collect();
+                self.set_upgrade_models(models);
+                (ConfigParameter::UpgradeModels, old, models.iter().map(|&m| m as u64).collect())
+            }
+            ConfigChange::ReinvestFee(percent) => {
+                let old = vec![self.reinvest_fee_percent as u64];
+                self.set_reinvest_fee(percent)?;
+                (ConfigParameter::ReinvestFee, old, vec![percent as u64])
+            }
+            ConfigChange::MaxAccrualDays(days) => {
+                let old = vec![self.max_accrual_days as u64];
+                self.set_max_accrual_days(days)?;
+                (ConfigParameter::MaxAccrualDays, old, vec![days as u64])
+            }
+            ConfigChange::MaxKeeperTip(max_tip_bps) => {
+                let old = vec![self.max_keeper_tip_bps as u64];
+                self.set_max_keeper_tip(max_tip_bps)?;
+                (ConfigParameter::MaxKeeperTip, old, vec![max_tip_bps as u64])
+            }
+        };
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRICE: u64 = 1_000_000;
+
+    #[test]
+    fn catalog_price_earns_full_rate() {
+        let config = GameConfig::new(Pubkey::new_unique(), 255);
+        assert_eq!(config.yield_weighted_principal(PRICE, PRICE), PRICE);
+        assert_eq!(config.yield_weighted_principal(PRICE, PRICE / 2), PRICE / 2);
+    }
+
+    #[test]
+    fn extra_catalog_prices_decay() {
+        let config = GameConfig::new(Pubkey::new_unique(), 255);
+        // 100% + 90% + 80%
+        assert_eq!(config.yield_weighted_principal(PRICE, 3 * PRICE), 27 * PRICE / 10);
+    }
+
+    #[test]
+    fn decay_stops_at_the_floor() {
+        let config = GameConfig::new(Pubkey::new_unique(), 255);
+        // 100% + 90..30% (7 цен) + 12 цен по floor 25%
+        assert_eq!(config.yield_weighted_principal(PRICE, 20 * PRICE), 82 * PRICE / 10);
+    }
+
+    #[test]
+    fn zero_decay_returns_principal() {
+        let mut config = GameConfig::new(Pubkey::new_unique(), 255);
+        config.yield_decay_percent = 0;
+        config.yield_floor_percent = 0;
+        assert_eq!(config.yield_weighted_principal(1, u64::MAX), u64::MAX);
+    }
+}
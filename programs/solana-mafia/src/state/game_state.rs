@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::error::SolanaMafiaError;
 
 #[account]
 pub struct GameState {
@@ -12,6 +14,24 @@ pub struct GameState {
     pub is_paused: bool,
     pub created_at: i64,
     pub bump: u8,
+    /// Операции, остановленные независимо от is_paused (биты PAUSE_*)
+    pub pause_flags: u8,
+    /// Операции, разрешенные даже при is_paused (биты PAUSE_*)
+    pub pause_allowlist: u8,
+    /// Запланированный новый treasury wallet (Pubkey::default() = нет)
+    pub pending_treasury_wallet: Pubkey,
+    /// Когда pending_treasury_wallet можно применить
+    pub treasury_change_available_at: i64,
+    /// Задержка смены treasury wallet в секундах
+    pub treasury_change_delay: i64,
+    /// Сумма невыплаченных долгов игрокам (owed_earnings)
+    pub total_owed: u64,
+    /// Pro-rata режим: резервов не хватает, выплаты частичные
+    pub pro_rata_mode: bool,
+    /// 🧾 FIFO очередь долгов: билет игрока, который погашается сейчас
+    pub debt_queue_head: u64,
+    /// 🧾 Следующий свободный номер билета
+    pub debt_queue_tail: u64,
 }
 
 impl GameState {
@@ -26,7 +46,16 @@ impl GameState {
         8 + // total_businesses
         1 + // is_paused
         8 + // created_at
-        1; // bump
+        1 + // bump
+        1 + // pause_flags
+        1 + // pause_allowlist
+        32 + // pending_treasury_wallet
+        8 + // treasury_change_available_at
+        8 + // treasury_change_delay
+        8 + // total_owed
+        1 + // pro_rata_mode
+        8 + // debt_queue_head
+        8; // debt_queue_tail
 
     /// Create new game state
     pub fn new(
@@ -46,6 +75,15 @@ impl GameState {
             is_paused: false,
             created_at,
             bump,
+            pause_flags: 0,
+            pause_allowlist: DEFAULT_PAUSE_ALLOWLIST,
+            pending_treasury_wallet: Pubkey::default(),
+            treasury_change_available_at: 0,
+            treasury_change_delay: DEFAULT_TREASURY_CHANGE_DELAY,
+            total_owed: 0,
+            pro_rata_mode: false,
+            debt_queue_head: 0,
+            debt_queue_tail: 0,
         }
     }
 
@@ -79,4 +117,216 @@ impl GameState {
         self.is_paused = !self.is_paused;
     }
 
-}
\ No newline at end of file
+    /// Set global pause, granular pause bits and pause allowlist
+    pub fn set_pause(&mut self, is_paused: bool, pause_flags: u8, pause_allowlist: u8) -> Result<()> {
+        if pause_flags & !PAUSE_ALL != 0 || pause_allowlist & !PAUSE_ALL != 0 {
+            return Err(ProgramError::InvalidArgument.into());
+        }
+        self.is_paused = is_paused;
+        self.pause_flags = pause_flags;
+        self.pause_allowlist = pause_allowlist;
+        Ok(())
+    }
+
+    /// Check that operation (PAUSE_* bit) is not paused
+    pub fn require_not_paused(&self, operation: u8) -> Result<()> {
+        if self.is_paused && self.pause_allowlist & operation == 0 {
+            return Err(SolanaMafiaError::EmergencyPauseActive.into());
+        }
+        if self.pause_flags & operation != 0 {
+            return Err(SolanaMafiaError::GamePaused.into());
+        }
+        Ok(())
+    }
+
+    /// Has a scheduled treasury wallet change
+    pub fn has_pending_treasury_change(&self) -> bool {
+        self.pending_treasury_wallet != Pubkey::default()
+    }
+
+    /// Schedule treasury wallet change after treasury_change_delay
+    pub fn schedule_treasury_wallet(&mut self, new_wallet: Pubkey, current_time: i64) -> Result<i64> {
+        if new_wallet == Pubkey::default() {
+            return Err(ProgramError::InvalidArgument.into());
+        }
+        let available_at = current_time
+            .checked_add(self.treasury_change_delay)
+            .ok_or(SolanaMafiaError::MathOverflow)?;
+        self.pending_treasury_wallet = new_wallet;
+        self.treasury_change_available_at = available_at;
+        Ok(available_at)
+    }
+
+    /// Cancel scheduled treasury wallet change, returns cancelled wallet
+    pub fn cancel_treasury_wallet_change(&mut self) -> Result<Pubkey> {
+        if !self.has_pending_treasury_change() {
+            return Err(SolanaMafiaError::NoPendingTreasuryChange.into());
+        }
+        let cancelled = self.pending_treasury_wallet;
+        self.pending_treasury_wallet = Pubkey::default();
+        self.treasury_change_available_at = 0;
+        Ok(cancelled)
+    }
+
+    /// Apply scheduled treasury wallet change once timelock expired, returns old wallet
+    pub fn apply_treasury_wallet_change(&mut self, current_time: i64) -> Result<Pubkey> {
+        if !self.has_pending_treasury_change() {
+            return Err(SolanaMafiaError::NoPendingTreasuryChange.into());
+        }
+        if current_time < self.treasury_change_available_at {
+            return Err(SolanaMafiaError::TreasuryChangeNotReady.into());
+        }
+        let old_wallet = self.treasury_wallet;
+        self.treasury_wallet = self.pending_treasury_wallet;
+        self.pending_treasury_wallet = Pubkey::default();
+        self.treasury_change_available_at = 0;
+        Ok(old_wallet)
+    }
+
+    /// Update treasury wallet change delay (applies to future schedules)
+    pub fn set_treasury_change_delay(&mut self, delay: i64) -> Result<()> {
+        require!(
+            (MIN_TREASURY_CHANGE_DELAY..=MAX_TREASURY_CHANGE_DELAY).contains(&delay),
+            SolanaMafiaError::InvalidTimelockDelay
+        );
+        self.treasury_change_delay = delay;
+        Ok(())
+    }
+
+    /// 🏦 Рассчитать выплату из treasury, не опуская его ниже rent-exempt минимума.
+    ///
+    /// Нормальный режим: выплата целиком, если свободных резервов хватает.
+    /// Иначе включается pro-rata режим: каждая выплата ограничена долей
+    /// `max_draw_bps` от свободных резервов, остаток записывается в долг.
+    /// Режим выключается, когда резервы снова покрывают все долги и выплату.
+    /// Возвращает (paid, shortfall) - долг записывает вызывающий (add_debt).
+    pub fn plan_payout(&mut self, amount: u64, available: u64, max_draw_bps: u16) -> Result<(u64, u64)> {
+        let required = self.total_owed
+            .checked_add(amount)
+            .ok_or(SolanaMafiaError::MathOverflow)?;
+        if self.pro_rata_mode && available >= required {
+            self.pro_rata_mode = false;
+        }
+
+        if !self.pro_rata_mode && amount <= available {
+            return Ok((amount, 0));
+        }
+
+        self.pro_rata_mode = true;
+        let max_draw = (available as u128 * max_draw_bps as u128 / 10_000) as u64;
+        let paid = amount.min(max_draw);
+        Ok((paid, amount - paid))
+    }
+
+    /// 🧾 Выдать билет в конец очереди долгов
+    pub fn issue_debt_ticket(&mut self) -> Result<u64> {
+        let ticket = self.debt_queue_tail;
+        self.debt_queue_tail = ticket
+            .checked_add(1)
+            .ok_or(SolanaMafiaError::MathOverflow)?;
+        Ok(ticket)
+    }
+
+    /// Учесть новый долг
+    pub fn add_debt(&mut self, amount: u64) -> Result<()> {
+        self.total_owed = self.total_owed
+            .checked_add(amount)
+            .ok_or(SolanaMafiaError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Учесть погашение долга. Полностью погашенный билет сдвигает голову очереди,
+    /// нулевой долг выключает pro-rata режим.
+    pub fn repay_debt(&mut self, amount: u64, ticket_closed: bool) -> Result<()> {
+        self.total_owed = self.total_owed
+            .checked_sub(amount)
+            .ok_or(SolanaMafiaError::MathOverflow)?;
+        if ticket_closed {
+            self.debt_queue_head = self.debt_queue_head
+                .checked_add(1)
+                .ok_or(SolanaMafiaError::MathOverflow)?;
+        }
+        if self.total_owed == 0 {
+            self.pro_rata_mode = false;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game_state() -> GameState {
+        GameState::new(Pubkey::new_unique(), Pubkey::new_unique(), 0, 255)
+    }
+
+    #[test]
+    fn pays_in_full_while_reserves_cover_the_payout() {
+        let mut state = game_state();
+        assert_eq!(state.plan_payout(500, 1_000, 5_000).unwrap(), (500, 0));
+        assert!(!state.pro_rata_mode);
+    }
+
+    #[test]
+    fn enters_pro_rata_when_reserves_run_short() {
+        let mut state = game_state();
+        // Не хватает на выплату: максимум 50% свободных резервов, остаток в долг
+        assert_eq!(state.plan_payout(2_000, 1_000, 5_000).unwrap(), (500, 1_500));
+        assert!(state.pro_rata_mode);
+    }
+
+    #[test]
+    fn stays_in_pro_rata_until_reserves_cover_all_debts() {
+        let mut state = game_state();
+        state.plan_payout(2_000, 1_000, 5_000).unwrap();
+        state.add_debt(1_500).unwrap();
+
+        // Выплата помещается в резервы, но вместе с долгами - нет
+        assert_eq!(state.plan_payout(100, 1_000, 5_000).unwrap(), (100, 0));
+        assert!(state.pro_rata_mode);
+        assert_eq!(state.plan_payout(800, 1_000, 5_000).unwrap(), (500, 300));
+        assert!(state.pro_rata_mode);
+
+        // Резервы покрывают долги + выплату - режим выключается
+        assert_eq!(state.plan_payout(100, 1_600, 5_000).unwrap(), (100, 0));
+        assert!(!state.pro_rata_mode);
+    }
+
+    #[test]
+    fn repaying_all_debt_exits_pro_rata() {
+        let mut state = game_state();
+        state.plan_payout(2_000, 1_000, 5_000).unwrap();
+        state.add_debt(1_500).unwrap();
+
+        state.repay_debt(1_000, false).unwrap();
+        assert!(state.pro_rata_mode);
+        state.repay_debt(500, true).unwrap();
+        assert!(!state.pro_rata_mode);
+        assert_eq!(state.total_owed, 0);
+        assert!(state.repay_debt(1, false).is_err());
+    }
+
+    #[test]
+    fn debt_queue_is_first_in_first_out() {
+        let mut state = game_state();
+        let first = state.issue_debt_ticket().unwrap();
+        let second = state.issue_debt_ticket().unwrap();
+        let third = state.issue_debt_ticket().unwrap();
+        assert_eq!((first, second, third), (0, 1, 2));
+        state.add_debt(300).unwrap();
+
+        // Частичное погашение не сдвигает голову
+        assert_eq!(state.debt_queue_head, first);
+        state.repay_debt(50, false).unwrap();
+        assert_eq!(state.debt_queue_head, first);
+
+        // Закрытый билет передает очередь следующему по порядку выдачи
+        state.repay_debt(50, true).unwrap();
+        assert_eq!(state.debt_queue_head, second);
+        state.repay_debt(100, true).unwrap();
+        assert_eq!(state.debt_queue_head, third);
+        state.repay_debt(100, true).unwrap();
+        assert_eq!(state.debt_queue_head, state.debt_queue_tail);
+    }
+}
@@ -1,14 +1,24 @@
 // programs/solana-mafia/src/state/mod.rs
+pub mod admin_council;
 pub mod business;
+pub mod claim_delegate;
+pub mod fee_split;
 pub mod game_config;
 pub mod game_state;  
+pub mod pending_config_change;
 pub mod player;
+pub mod proposal;
 pub mod treasury; 
 
+pub use admin_council::*;
 pub use business::*;
+pub use claim_delegate::*;
+pub use fee_split::*;
 pub use game_config::*;
 pub use game_state::*;
+pub use pending_config_change::*;
 pub use player::*;
+pub use proposal::*;
 pub use treasury::Treasury;
 
 
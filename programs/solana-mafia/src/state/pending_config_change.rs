@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+
+/// Изменение GameConfig, которое можно поставить в очередь с задержкой
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum ConfigChange {
+    EntryFee(u64),
+    BusinessRates([u16; BUSINESS_TYPES_COUNT]),
+    MinDeposits([u64; BUSINESS_TYPES_COUNT]),
+    UpgradeSchedule {
+        max_level: u8,
+        cost_multipliers: [u16; MAX_UPGRADE_LEVEL as usize],
+        bonuses: [u16; MAX_UPGRADE_LEVEL as usize],
+    },
+    FeeSplit {
+        treasury_fee_percent: u8,
+        claim_fee_percent: u8,
+    },
+    SlotCosts {
+        slot_unlock_cost_percent: u8,
+        premium_slot_costs: [u64; 3],
+        premium_slot_yield_bonuses: [u16; 3],
+        premium_slot_sell_fee_discounts: [u8; 3],
+    },
+    SellFees([u8; EARLY_SELL_FEES_LEN]),
+    AutoClaimTiers {
+        days: [u16; AUTO_CLAIM_TIERS],
+        costs: [u64; AUTO_CLAIM_TIERS],
+    },
+    RegistrationsOpen(bool),
+    ConfigTimelock(i64),
+    ProRataMaxDraw(u16),
+    ReferralProgram {
+        depth: u8,
+        bonus_bps: [u16; MAX_REFERRAL_DEPTH],
+    },
+    DepositTiers {
+        strict_pricing: bool,
+        tiers: [u16; MAX_DEPOSIT_TIERS],
+    },
+    UpgradeModels([UpgradeModel; BUSINESS_TYPES_COUNT]),
+    ReinvestFee(u8),
+    MaxAccrualDays(u16),
+    MaxKeeperTip(u16),
+    YieldCurve {
+        max_deposits: [u64; BUSINESS_TYPES_COUNT],
+        decay_percent: u8,
+        floor_percent: u8,
+    },
+}
+
+impl ConfigChange {
+    /// Максимальный размер (самый большой вариант - YieldCurve)
+    pub const MAX_SIZE: usize = 1 + 8 * BUSINESS_TYPES_COUNT + 2;
+}
+
+/// ⏳ Отложенное изменение GameConfig - выполняется не раньше executable_at
+#[account]
+pub struct PendingConfigChange {
+    pub id: u64,
+    pub change: ConfigChange,
+    pub queued_by: Pubkey,
+    pub queued_at: i64,
+    pub executable_at: i64,
+    pub bump: u8,
+}
+
+impl PendingConfigChange {
+    pub const SIZE: usize = 8 + // discriminator
+        8 + // id
+        ConfigChange::MAX_SIZE + // change
+        32 + // queued_by
+        8 + // queued_at
+        8 + // executable_at
+        1; // bump
+}
@@ -2,6 +2,7 @@
 use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::state::business::Business;
+use crate::state::game_config::GameConfig;
 use crate::error::SolanaMafiaError;
 
 /// 🚀 ULTRA-OPTIMIZED: Битовые флаги для слотов
@@ -144,19 +145,12 @@ impl BusinessSlotCompact {
     }
     
     /// Получить стоимость слота для первой оплаты
-    pub fn get_slot_cost(&self, business_price: u64) -> u64 {
-        match self.slot_type() {
-            SlotType::Basic => {
-                // Слоты 0-2 бесплатные, слоты 3-5 = 10% от цены бизнеса
-                if self.is_paid() {
-                    0 // Уже оплачен
-                } else {
-                    business_price * 10 / 100 // 10%
-                }
-            },
-            SlotType::Premium => if self.is_paid() { 0 } else { PREMIUM_SLOT_COSTS[0] },
-            SlotType::VIP => if self.is_paid() { 0 } else { PREMIUM_SLOT_COSTS[1] },
-            SlotType::Legendary => if self.is_paid() { 0 } else { PREMIUM_SLOT_COSTS[2] },
+    pub fn get_slot_cost(&self, business_price: u64, config: &GameConfig) -> u64 {
+        // Слоты 0-2 бесплатные (уже оплачены), слоты 3-5 = % от цены бизнеса, премиум = фикс. цена
+        if self.is_paid() {
+            0 // Уже оплачен
+        } else {
+            config.get_slot_cost(self.slot_type(), business_price)
         }
     }
 
@@ -179,29 +173,19 @@ impl BusinessSlotCompact {
     }
 
     /// Получить бонус доходности слота
-    pub fn get_yield_bonus(&self) -> u16 {
-        match self.slot_type() {
-            SlotType::Basic => 0,
-            SlotType::Premium => PREMIUM_SLOT_YIELD_BONUSES[0],  // +1.5%
-            SlotType::VIP => PREMIUM_SLOT_YIELD_BONUSES[1],      // +3%
-            SlotType::Legendary => PREMIUM_SLOT_YIELD_BONUSES[2], // +5%
-        }
+    pub fn get_yield_bonus(&self, config: &GameConfig) -> u16 {
+        config.get_slot_yield_bonus(self.slot_type())
     }
 
     /// Получить скидку на комиссию продажи
-    pub fn get_sell_fee_discount(&self) -> u8 {
-        match self.slot_type() {
-            SlotType::Basic => 0,
-            SlotType::Premium => PREMIUM_SLOT_SELL_FEE_DISCOUNTS[0],  // 0%
-            SlotType::VIP => PREMIUM_SLOT_SELL_FEE_DISCOUNTS[1],      // -50%
-            SlotType::Legendary => PREMIUM_SLOT_SELL_FEE_DISCOUNTS[2], // -100%
-        }
+    pub fn get_sell_fee_discount(&self, config: &GameConfig) -> u8 {
+        config.get_slot_sell_fee_discount(self.slot_type())
     }
 
     /// Рассчитать доходность с учетом бонуса слота
-    pub fn calculate_earnings(&self, base_earnings: u64) -> u64 {
+    pub fn calculate_earnings(&self, base_earnings: u64, config: &GameConfig) -> u64 {
         if let Some(_business) = &self.business {
-            let slot_bonus = self.get_yield_bonus();
+            let slot_bonus = self.get_yield_bonus(config);
             let bonus_earnings = (base_earnings as u128 * slot_bonus as u128) / 10000;
             base_earnings + bonus_earnings as u64
         } else {
@@ -234,13 +218,37 @@ pub struct PlayerCompact {
     pub total_earned: u64,          // u64 - накопленные earnings могут быть большими
     
     /// 🆕 НОВАЯ СИСТЕМА CLAIM
-    pub auto_claim_purchased: bool, // Купил ли автонакопления за 0.05 SOL
+    pub auto_claim_purchased: bool, // Legacy: бессрочные автонакопления (до подписок)
     
     /// 🆕 u32 TIMESTAMPS вместо i64 (до 2106 года) 
     pub created_at: u32,
     pub first_business_time: u32,
     
     pub bump: u8,
+    
+    /// 🏦 Невыплаченный остаток (pro-rata режим treasury)
+    pub owed_earnings: u64,
+    
+    /// 🧾 Билет в очереди долгов (действителен, пока owed_earnings > 0)
+    pub debt_ticket: u64,
+    
+    /// 🤝 Кто пригласил игрока (Pubkey::default() = никто)
+    pub referrer: Pubkey,
+    
+    /// 🤝 Накопленные реферальные бонусы (лежат на этом Player PDA)
+    pub pending_referral_earnings: u64,
+    
+    /// 🤝 Всего получено реферальных бонусов
+    pub total_referral_earned: u64,
+    
+    /// 🔁 Начало текущей подписки auto-claim
+    pub auto_claim_started_at: i64,
+    
+    /// 🔁 Конец подписки auto-claim (0 = не покупалась)
+    pub auto_claim_expires_at: i64,
+    
+    /// 🔁 Earnings, зафиксированные при продлении истекшей подписки (выплачиваются следующим claim)
+    pub banked_earnings: u64,
 }
 
 impl PlayerCompact {
@@ -261,7 +269,15 @@ impl PlayerCompact {
         1 + // auto_claim_purchased (bool)
         4 + // created_at (u32)
         4 + // first_business_time (u32)
-        1; // bump
+        1 + // bump
+        8 + // owed_earnings (u64)
+        8 + // debt_ticket (u64)
+        32 + // referrer (Pubkey)
+        8 + // pending_referral_earnings (u64)
+        8 + // total_referral_earned (u64)
+        8 + // auto_claim_started_at (i64)
+        8 + // auto_claim_expires_at (i64)
+        8; // banked_earnings (u64)
 
     pub fn has_paid_entry(&self) -> bool {
         (self.flags & Self::HAS_PAID_ENTRY_FLAG) != 0
@@ -294,13 +310,13 @@ impl PlayerCompact {
         let mut slots = [BusinessSlotCompact::new_basic_free(); 9];
         
         // Слоты 0-2: Basic бесплатные (уже оплачены)
-        for i in 0..3 {
-            slots[i] = BusinessSlotCompact::new_basic_free();
+        for slot in slots.iter_mut().take(3) {
+            *slot = BusinessSlotCompact::new_basic_free();
         }
         
         // Слоты 3-5: Basic платные (10% при первом использовании)
-        for i in 3..6 {
-            slots[i] = BusinessSlotCompact::new_basic_paid();
+        for slot in slots.iter_mut().take(6).skip(3) {
+            *slot = BusinessSlotCompact::new_basic_paid();
         }
         
         // Слоты 6-8: Premium/VIP/Legendary (неоплаченные)
@@ -322,42 +338,115 @@ impl PlayerCompact {
             created_at: Self::timestamp_to_u32(current_time),
             first_business_time: 0,
             bump,
+            owed_earnings: 0,
+            debt_ticket: 0,
+            referrer: Pubkey::default(),
+            pending_referral_earnings: 0,
+            total_referral_earned: 0,
+            auto_claim_started_at: 0,
+            auto_claim_expires_at: 0,
+            banked_earnings: 0,
+        }
+    }
+
+    /// 🤝 Начислить реферальный бонус
+    pub fn add_referral_bonus(&mut self, amount: u64) -> Result<()> {
+        self.pending_referral_earnings = self.pending_referral_earnings
+            .checked_add(amount)
+            .ok_or(SolanaMafiaError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// 🤝 Забрать реферальные бонусы, возвращает сумму
+    pub fn claim_referral_bonus(&mut self) -> Result<u64> {
+        let amount = self.pending_referral_earnings;
+        if amount == 0 {
+            return Err(SolanaMafiaError::NoReferralBonus.into());
         }
+        self.pending_referral_earnings = 0;
+        self.total_referral_earned = self.total_referral_earned
+            .checked_add(amount)
+            .ok_or(SolanaMafiaError::MathOverflow)?;
+        Ok(amount)
+    }
+
+    /// 🧾 Есть ли непогашенный долг (и, значит, билет в очереди)
+    pub fn has_debt(&self) -> bool {
+        self.owed_earnings > 0
+    }
+
+    /// 🏦 Записать невыплаченный остаток в долг
+    pub fn add_owed_earnings(&mut self, amount: u64) -> Result<()> {
+        self.owed_earnings = self.owed_earnings
+            .checked_add(amount)
+            .ok_or(SolanaMafiaError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// 🧾 Погасить часть долга, возвращает true если долг закрыт полностью
+    pub fn repay_owed_earnings(&mut self, amount: u64) -> Result<bool> {
+        self.owed_earnings = self.owed_earnings
+            .checked_sub(amount)
+            .ok_or(SolanaMafiaError::MathOverflow)?;
+        Ok(self.owed_earnings == 0)
     }
 
     /// 🆕 Рассчитать общие claimable earnings от всех бизнесов  
-    pub fn calculate_total_claimable_earnings(&self, current_time: i64) -> u64 {
-        let mut total_earnings = 0u64;
+    pub fn calculate_total_claimable_earnings(&self, current_time: i64, config: &GameConfig) -> u64 {
+        let mut total_earnings = self.banked_earnings;
         
-        for slot in &self.business_slots {
-            if let Some(business) = &slot.business {
-                let base_earnings = if self.auto_claim_purchased {
-                    // С автонакоплениями: точный расчет по времени
-                    business.calculate_claimable_earnings(current_time)
-                } else {
-                    // Без автонакоплений: полная суточная доходность
-                    business.calculate_full_daily_earnings_if_active()
-                };
-                
-                // Применяем бонус доходности слота
-                let slot_earnings = slot.calculate_earnings(base_earnings);
-                total_earnings += slot_earnings;
-            }
+        for slot_index in 0..self.business_slots.len() {
+            total_earnings += self.calculate_slot_claimable_earnings(slot_index, current_time, config);
         }
         
         total_earnings
     }
 
+    /// 🆕 Claimable earnings одного бизнеса (с бонусом слота)
+    pub fn calculate_slot_claimable_earnings(&self, slot_index: usize, current_time: i64, config: &GameConfig) -> u64 {
+        let slot = match self.business_slots.get(slot_index) {
+            Some(slot) => slot,
+            None => return 0,
+        };
+        let business = match &slot.business {
+            Some(business) => business,
+            None => return 0,
+        };
+
+        // В окне auto-claim - точный расчет по времени, вне окна - завершенные 24h периоды
+        let base_earnings = business.calculate_accrued_earnings(current_time, self.auto_claim_window(), config);
+
+        // Применяем бонус доходности слота
+        slot.calculate_earnings(base_earnings, config)
+    }
+
     /// 🆕 Обновить времена claim для всех бизнесов после claim
     pub fn update_all_business_claim_times(&mut self, current_time: i64) -> Result<()> {
+        let auto_window = self.auto_claim_window();
         for slot in &mut self.business_slots {
             if let Some(business) = &mut slot.business {
-                business.update_claim_time(current_time);
+                business.settle_claim(current_time, auto_window);
             }
         }
         Ok(())
     }
 
+    /// 🏦 Зафиксировать накопления одного бизнеса в banked_earnings по текущей ставке.
+    /// Вызывается перед любым изменением ставки или депозита бизнеса.
+    pub fn bank_slot_earnings(&mut self, slot_index: usize, current_time: i64, config: &GameConfig) -> Result<()> {
+        let accrued = self.calculate_slot_claimable_earnings(slot_index, current_time, config);
+        self.banked_earnings = self.banked_earnings
+            .checked_add(accrued)
+            .ok_or(SolanaMafiaError::MathOverflow)?;
+
+        let auto_window = self.auto_claim_window();
+        let business = self.business_slots.get_mut(slot_index)
+            .and_then(|slot| slot.business.as_mut())
+            .ok_or(SolanaMafiaError::BusinessNotFound)?;
+        business.settle_claim(current_time, auto_window);
+        Ok(())
+    }
+
     /// Получить количество активных бизнесов
     pub fn get_active_businesses_count(&self) -> u8 {
         self.business_slots.iter()
@@ -381,14 +470,44 @@ impl PlayerCompact {
         Ok(())
     }
 
-    /// 🆕 Купить автонакопления за 0.05 SOL
-    pub fn purchase_auto_claim(&mut self) -> Result<()> {
+    /// 🔁 Активна ли подписка auto-claim
+    pub fn has_auto_claim(&self, current_time: i64) -> bool {
+        self.auto_claim_purchased || current_time < self.auto_claim_expires_at
+    }
+
+    /// 🔁 Окно auto-claim [start, end) для расчета накоплений
+    pub fn auto_claim_window(&self) -> (i64, i64) {
+        if self.auto_claim_purchased {
+            (0, i64::MAX)
+        } else {
+            (self.auto_claim_started_at, self.auto_claim_expires_at)
+        }
+    }
+
+    /// 🔁 Купить / продлить подписку auto-claim, возвращает новое время окончания.
+    /// Активная подписка продлевается (стакается), истекшая - начинается заново;
+    /// перед этим накопления по старому окну фиксируются в banked_earnings.
+    pub fn purchase_auto_claim(&mut self, days: u16, current_time: i64, config: &GameConfig) -> Result<i64> {
         if self.auto_claim_purchased {
             return Err(SolanaMafiaError::AutoClaimAlreadyPurchased.into());
         }
-        
-        self.auto_claim_purchased = true;
-        Ok(())
+
+        let duration = days as i64 * EARNINGS_INTERVAL;
+        if self.has_auto_claim(current_time) {
+            self.auto_claim_expires_at = self.auto_claim_expires_at
+                .checked_add(duration)
+                .ok_or(SolanaMafiaError::MathOverflow)?;
+        } else {
+            let accrued = self.calculate_total_claimable_earnings(current_time, config);
+            self.banked_earnings = accrued;
+            self.update_all_business_claim_times(current_time)?;
+            self.auto_claim_started_at = current_time;
+            self.auto_claim_expires_at = current_time
+                .checked_add(duration)
+                .ok_or(SolanaMafiaError::MathOverflow)?;
+        }
+
+        Ok(self.auto_claim_expires_at)
     }
 
     /// Улучшить бизнес в слоте
@@ -403,27 +522,28 @@ impl PlayerCompact {
     }
 
     /// Продать бизнес из слота
-    pub fn sell_business_from_slot(&mut self, slot_index: usize) -> Result<(Business, u8)> {
+    pub fn sell_business_from_slot(&mut self, slot_index: usize, config: &GameConfig) -> Result<(Business, u8)> {
         if slot_index >= self.business_slots.len() {
             return Err(SolanaMafiaError::InvalidSlotIndex.into());
         }
 
         let business = self.business_slots[slot_index].remove_business()
             .ok_or(SolanaMafiaError::SlotEmpty)?;
-        let discount = self.business_slots[slot_index].get_sell_fee_discount();
+        let discount = self.business_slots[slot_index].get_sell_fee_discount(config);
         
         Ok((business, discount))
     }
 
     /// 🆕 Получить сумму для claim с учетом новой системы
-    pub fn get_claimable_amount(&self, current_time: i64) -> Result<u64> {
-        Ok(self.calculate_total_claimable_earnings(current_time))
+    pub fn get_claimable_amount(&self, current_time: i64, config: &GameConfig) -> Result<u64> {
+        Ok(self.calculate_total_claimable_earnings(current_time, config))
     }
 
     /// 🆕 Обработать claim earnings - обновить статистику и времена
     pub fn process_claim(&mut self, claimed_amount: u64, current_time: i64) -> Result<()> {
         // Обновляем общую статистику заработанного
         self.total_earned = self.total_earned.saturating_add(claimed_amount);
+        self.banked_earnings = 0;
         
         // Обновляем времена последнего claim для всех бизнесов
         self.update_all_business_claim_times(current_time)?;
@@ -431,29 +551,46 @@ impl PlayerCompact {
         Ok(())
     }
 
+    /// 🆕 Обработать claim одного слота - обновить статистику и время только этого бизнеса
+    pub fn process_slot_claim(&mut self, slot_index: usize, claimed_amount: u64, current_time: i64) -> Result<()> {
+        let auto_window = self.auto_claim_window();
+        let business = self.business_slots.get_mut(slot_index)
+            .and_then(|slot| slot.business.as_mut())
+            .ok_or(SolanaMafiaError::BusinessNotFound)?;
+        business.settle_claim(current_time, auto_window);
+
+        self.total_earned = self.total_earned.saturating_add(claimed_amount);
+        Ok(())
+    }
+
+    /// 🆕 Можно ли клэймить один слот без автонакоплений (завершен хотя бы один 24h период)
+    pub fn can_claim_slot_without_auto(&self, slot_index: usize, current_time: i64) -> bool {
+        if self.has_auto_claim(current_time) {
+            return true;
+        }
+
+        self.business_slots.get(slot_index)
+            .and_then(|slot| slot.business.as_ref())
+            .is_some_and(|business| business.completed_periods(current_time, self.auto_claim_window()) > 0)
+    }
+
     /// 🆕 Проверить можно ли клэймить (для пользователей без автонакоплений)
     pub fn can_claim_without_auto(&self, current_time: i64) -> bool {
-        // Если автонакопления куплены - клэймить можно всегда
-        if self.auto_claim_purchased {
+        // Если подписка активна или есть зафиксированные earnings - клэймить можно всегда
+        if self.has_auto_claim(current_time) || self.banked_earnings > 0 {
             return true;
         }
         
-        // Для пользователей без автонакоплений - проверяем был ли недавний claim
-        // Находим самый последний claim среди всех бизнесов (claim затрагивает все бизнесы)
-        let latest_claim = self.business_slots.iter()
-            .filter_map(|slot| slot.business.as_ref())
-            .filter_map(|business| business.last_claim_at)
-            .map(|time| crate::state::business::Business::u32_to_timestamp(time))
-            .max()
-            .unwrap_or(0);
-            
-        // Должно пройти минимум 24 часа с последнего claim для получения полной суточной доходности
-        current_time - latest_claim >= EARNINGS_INTERVAL
+        // Для пользователей без автонакоплений - хотя бы у одного бизнеса завершен 24h период
+        (0..self.business_slots.len()).any(|slot_index| self.can_claim_slot_without_auto(slot_index, current_time))
     }
 
     /// Health check
-    pub fn health_check(&self, _current_time: i64) -> Result<()> {
-        // Simplified health check
+    pub fn health_check(&self, current_time: i64) -> Result<()> {
+        // Проверяем каждый бизнес (включая лимит ставки после улучшений)
+        for business in self.get_all_businesses() {
+            business.health_check(current_time)?;
+        }
         Ok(())
     }
 
@@ -465,21 +602,21 @@ impl PlayerCompact {
     }
 
     /// 🏪 Получить стоимость слота для бизнеса (новая система)
-    pub fn get_slot_cost_for_business(&self, slot_index: usize, business_price: u64) -> u64 {
+    pub fn get_slot_cost_for_business(&self, slot_index: usize, business_price: u64, config: &GameConfig) -> u64 {
         if slot_index >= self.business_slots.len() {
             return 0;
         }
         
-        self.business_slots[slot_index].get_slot_cost(business_price)
+        self.business_slots[slot_index].get_slot_cost(business_price, config)
     }
 
     /// 🏪 Оплатить слот при первом использовании (новая система)
-    pub fn pay_slot_if_needed(&mut self, slot_index: usize, business_price: u64) -> Result<u64> {
+    pub fn pay_slot_if_needed(&mut self, slot_index: usize, business_price: u64, config: &GameConfig) -> Result<u64> {
         if slot_index >= self.business_slots.len() {
             return Err(SolanaMafiaError::InvalidSlotIndex.into());
         }
         
-        let slot_cost = self.business_slots[slot_index].get_slot_cost(business_price);
+        let slot_cost = self.business_slots[slot_index].get_slot_cost(business_price, config);
         
         if slot_cost > 0 {
             self.business_slots[slot_index].pay_slot(slot_cost)?;
@@ -490,8 +627,8 @@ impl PlayerCompact {
     }
 
     /// 🆕 Получить данные для фронтенда (совместимость с новой системой)
-    pub fn get_frontend_data(&self, current_time: i64) -> crate::PlayerFrontendData {
-        let claimable_earnings = self.calculate_total_claimable_earnings(current_time);
+    pub fn get_frontend_data(&self, current_time: i64, config: &GameConfig) -> crate::PlayerFrontendData {
+        let claimable_earnings = self.calculate_total_claimable_earnings(current_time, config);
         let active_businesses = self.get_active_businesses_count();
         let can_claim = self.can_claim_without_auto(current_time);
 
@@ -500,9 +637,12 @@ impl PlayerCompact {
             total_invested: self.total_invested,
             total_earned: self.total_earned,
             claimable_earnings,
+            owed_earnings: self.owed_earnings,
+            pending_referral_earnings: self.pending_referral_earnings,
             businesses_count: active_businesses,
             active_businesses,
-            auto_claim_purchased: self.auto_claim_purchased,
+            auto_claim_purchased: self.has_auto_claim(current_time),
+            auto_claim_expires_at: self.auto_claim_expires_at,
             can_claim,
         }
     }
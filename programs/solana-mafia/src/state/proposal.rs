@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::error::SolanaMafiaError;
+use crate::state::admin_council::AdminCouncil;
+use crate::state::fee_split::FeeRecipient;
+use crate::state::pending_config_change::ConfigChange;
+
+/// Чувствительная операция, которую совет должен одобрить.
+/// Аргументы должны точно совпадать с аргументами инструкции.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum AdminAction {
+    QueueConfigChange(ConfigChange),
+    SetFeeRecipients(Vec<FeeRecipient>),
+    SetPause {
+        is_paused: bool,
+        pause_flags: u8,
+        pause_allowlist: u8,
+    },
+    ScheduleTreasuryWallet(Pubkey),
+    SetTreasuryChangeDelay(i64),
+    ProposeAuthority(Pubkey),
+    RenounceAuthority,
+    SetAdminCouncil {
+        members: Vec<Pubkey>,
+        threshold: u8,
+    },
+    CloseRegistrations,
+    /// id отменяемого PendingConfigChange
+    CancelConfigChange(u64),
+    /// Отменяемый pending treasury wallet
+    CancelTreasuryWalletChange(Pubkey),
+}
+
+impl AdminAction {
+    /// Максимальный размер (самый большой вариант - SetAdminCouncil)
+    pub const MAX_SIZE: usize = 1 + 4 + 32 * MAX_COUNCIL_MEMBERS + 1;
+}
+
+/// 🗳️ Предложение совета - одноразовый "билет" на выполнение AdminAction
+#[account]
+pub struct Proposal {
+    pub id: u64,
+    pub council_version: u64,
+    pub action: AdminAction,
+    pub proposer: Pubkey,
+    /// Битовая маска одобрений по индексу участника совета
+    pub approvals: u8,
+    pub approval_count: u8,
+    pub created_at: i64,
+    pub executed: bool,
+    pub executed_at: i64,
+    pub bump: u8,
+}
+
+impl Proposal {
+    pub const SIZE: usize = 8 + // discriminator
+        8 + // id
+        8 + // council_version
+        AdminAction::MAX_SIZE + // action
+        32 + // proposer
+        1 + // approvals
+        1 + // approval_count
+        8 + // created_at
+        1 + // executed
+        8 + // executed_at
+        1; // bump
+
+    /// Одобрение участником с данным индексом
+    pub fn approve(&mut self, council: &AdminCouncil, member_index: usize) -> Result<()> {
+        require!(self.council_version == council.version, SolanaMafiaError::StaleProposal);
+        require!(!self.executed, SolanaMafiaError::ProposalAlreadyExecuted);
+
+        let bit = 1u8 << member_index;
+        require!(self.approvals & bit == 0, SolanaMafiaError::ProposalAlreadyApproved);
+
+        self.approvals |= bit;
+        self.approval_count += 1;
+        Ok(())
+    }
+
+    /// Проверить, что предложение одобрено и соответствует операции, и погасить его
+    pub fn consume(&mut self, council: &AdminCouncil, action: &AdminAction, now: i64) -> Result<()> {
+        require!(self.council_version == council.version, SolanaMafiaError::StaleProposal);
+        require!(!self.executed, SolanaMafiaError::ProposalAlreadyExecuted);
+        require!(self.approval_count >= council.threshold, SolanaMafiaError::ProposalNotApproved);
+        require!(self.action == *action, SolanaMafiaError::ProposalActionMismatch);
+
+        self.executed = true;
+        self.executed_at = now;
+        Ok(())
+    }
+}
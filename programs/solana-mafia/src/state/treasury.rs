@@ -13,4 +13,10 @@ impl Treasury {
     pub fn new(bump: u8) -> Self {
         Self { bump }
     }
+
+    /// Свободные резервы - все, что выше rent-exempt минимума
+    pub fn available_balance(treasury_info: &AccountInfo) -> Result<u64> {
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(Self::SIZE);
+        Ok(treasury_info.lamports().saturating_sub(rent_exempt_minimum))
+    }
 }
\ No newline at end of file